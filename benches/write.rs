@@ -0,0 +1,98 @@
+//! Command to run benches:
+//! `cargo +nightly bench --bench write`
+//!
+//! Compares binary payload written by [PLYFile::write], which encodes rows into a buffer,
+//! with the previous path writing value by value.
+//! Output is discarded, so only encoding is measured.
+
+#![feature(test)]
+
+use std::io::{BufWriter, Write};
+
+use ply::{
+    Element, Format, GenericElement, PLYFile, PLYValue, PLYValueTypeName, Payload, Property,
+};
+
+extern crate test;
+use test::Bencher;
+
+const VERTICES: usize = 200_000;
+
+/// Bytes of x, y, z float and red, green, blue uchar
+const ROW_BYTES: usize = 15;
+
+fn point_cloud() -> PLYFile {
+    let mut props = Property::new();
+    for name in ["x", "y", "z"] {
+        props.push_prop(name, PLYValueTypeName::Float);
+    }
+    for name in ["red", "green", "blue"] {
+        props.push_prop(name, PLYValueTypeName::Uchar);
+    }
+    let mut element = GenericElement::new(props);
+    for i in 0..VERTICES {
+        let f = i as f32;
+        let c = i as u8;
+        element
+            .push_payload(Payload::new(vec![
+                f.into(),
+                (f + 0.5).into(),
+                (-f).into(),
+                c.into(),
+                c.wrapping_add(1).into(),
+                c.wrapping_add(2).into(),
+            ]))
+            .unwrap();
+    }
+    let mut ply = PLYFile::new(Format::BinaryLittleEndian {
+        version: "1.0".to_string(),
+    });
+    ply.elements.push(Element::Element {
+        name: "vertex".to_string(),
+        elements: element,
+    });
+    ply
+}
+
+/// In my environment, 800-810 MB/s (200000 vertex), a little slower than [write_binary_per_value]
+#[bench]
+fn write_binary_bulk(b: &mut Bencher) {
+    let ply = point_cloud();
+    b.bytes = (VERTICES * ROW_BYTES) as u64;
+    b.iter(|| {
+        let mut writer = BufWriter::new(std::io::sink());
+        ply.write(&mut writer).unwrap();
+        writer.into_inner().unwrap()
+    });
+}
+
+/// Previous path, `write` of each value to [BufWriter]
+///
+/// In my environment, 920-960 MB/s (200000 vertex)
+#[bench]
+fn write_binary_per_value(b: &mut Bencher) {
+    let ply = point_cloud();
+    let payload = match &ply.elements[0] {
+        Element::Element { elements, .. } => elements.payload(),
+        _ => unreachable!(),
+    };
+    b.bytes = (VERTICES * ROW_BYTES) as u64;
+    b.iter(|| {
+        let mut writer = BufWriter::new(std::io::sink());
+        for row in payload {
+            for v in row.iter() {
+                match v {
+                    PLYValue::Char(v) => writer.write(&v.to_le_bytes()).unwrap(),
+                    PLYValue::Uchar(v) => writer.write(&v.to_le_bytes()).unwrap(),
+                    PLYValue::Short(v) => writer.write(&v.to_le_bytes()).unwrap(),
+                    PLYValue::Ushort(v) => writer.write(&v.to_le_bytes()).unwrap(),
+                    PLYValue::Int(v) => writer.write(&v.to_le_bytes()).unwrap(),
+                    PLYValue::Uint(v) => writer.write(&v.to_le_bytes()).unwrap(),
+                    PLYValue::Float(v) => writer.write(&v.to_le_bytes()).unwrap(),
+                    PLYValue::Double(v) => writer.write(&v.to_le_bytes()).unwrap(),
+                };
+            }
+        }
+        writer.into_inner().unwrap()
+    });
+}
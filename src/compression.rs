@@ -0,0 +1,270 @@
+//! Compressed PLY files (features `gzip` and `zstd`)
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+use std::io::BufReader;
+use std::io::{self, BufRead, Read, Write};
+
+use crate::error::{PLYError, PLYResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Compression of whole PLY file
+pub enum Compression {
+    #[default]
+    None,
+    /// `.ply.gz`, requires feature `gzip`
+    Gzip,
+    /// `.ply.zst`, requires feature `zstd`
+    Zstd,
+}
+
+impl Compression {
+    /// Detect compression from magic bytes at the start of file
+    pub fn detect(head: &[u8]) -> Compression {
+        if head.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if head.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+
+    fn not_enabled(&self) -> String {
+        match self {
+            Compression::None => "no compression".to_string(),
+            Compression::Gzip => "gzip compression, feature gzip is not enabled".to_string(),
+            Compression::Zstd => "zstd compression, feature zstd is not enabled".to_string(),
+        }
+    }
+}
+
+/// Plain or decompressed input, detected by magic bytes
+pub(crate) enum Decompressed<R> {
+    Plain(R),
+    #[cfg(feature = "gzip")]
+    Gzip(BufReader<flate2::bufread::MultiGzDecoder<R>>),
+    #[cfg(feature = "zstd")]
+    Zstd(BufReader<zstd::Decoder<'static, R>>),
+}
+
+impl<R: BufRead> Decompressed<R> {
+    pub fn new(mut reader: R) -> PLYResult<Self> {
+        match Compression::detect(reader.fill_buf()?) {
+            Compression::None => Ok(Decompressed::Plain(reader)),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Ok(Decompressed::Gzip(BufReader::new(
+                flate2::bufread::MultiGzDecoder::new(reader),
+            ))),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Ok(Decompressed::Zstd(BufReader::new(
+                zstd::Decoder::with_buffer(reader)?,
+            ))),
+            #[allow(unreachable_patterns)]
+            compression => Err(PLYError::Unsupported(compression.not_enabled())),
+        }
+    }
+}
+
+impl<R: BufRead> Read for Decompressed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Decompressed::Plain(r) => r.read(buf),
+            #[cfg(feature = "gzip")]
+            Decompressed::Gzip(r) => r.read(buf),
+            #[cfg(feature = "zstd")]
+            Decompressed::Zstd(r) => r.read(buf),
+        }
+    }
+}
+
+impl<R: BufRead> BufRead for Decompressed<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match self {
+            Decompressed::Plain(r) => r.fill_buf(),
+            #[cfg(feature = "gzip")]
+            Decompressed::Gzip(r) => r.fill_buf(),
+            #[cfg(feature = "zstd")]
+            Decompressed::Zstd(r) => r.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match self {
+            Decompressed::Plain(r) => r.consume(amt),
+            #[cfg(feature = "gzip")]
+            Decompressed::Gzip(r) => r.consume(amt),
+            #[cfg(feature = "zstd")]
+            Decompressed::Zstd(r) => r.consume(amt),
+        }
+    }
+}
+
+/// Output compressed by [Compression], call [Compressed::finish] after writing
+pub(crate) enum Compressed<W: Write> {
+    Plain(W),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::Encoder<'static, W>),
+}
+
+impl<W: Write> Compressed<W> {
+    pub fn new(writer: W, compression: Compression) -> io::Result<Self> {
+        match compression {
+            Compression::None => Ok(Compressed::Plain(writer)),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Ok(Compressed::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            ))),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Ok(Compressed::Zstd(zstd::Encoder::new(writer, 0)?)),
+            #[allow(unreachable_patterns)]
+            compression => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                compression.not_enabled(),
+            )),
+        }
+    }
+
+    /// Write end of compressed stream
+    pub fn finish(self) -> io::Result<W> {
+        match self {
+            Compressed::Plain(w) => Ok(w),
+            #[cfg(feature = "gzip")]
+            Compressed::Gzip(w) => w.finish(),
+            #[cfg(feature = "zstd")]
+            Compressed::Zstd(w) => w.finish(),
+        }
+    }
+}
+
+impl<W: Write> Write for Compressed<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Compressed::Plain(w) => w.write(buf),
+            #[cfg(feature = "gzip")]
+            Compressed::Gzip(w) => w.write(buf),
+            #[cfg(feature = "zstd")]
+            Compressed::Zstd(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Compressed::Plain(w) => w.flush(),
+            #[cfg(feature = "gzip")]
+            Compressed::Gzip(w) => w.flush(),
+            #[cfg(feature = "zstd")]
+            Compressed::Zstd(w) => w.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::BufWriter;
+
+    use super::Compression;
+    use crate::{error::PLYError, Format, GenericElement, PLYFile, PLYHeader, Payload};
+    use crate::{Element, PLYValueTypeName, Property, WriteOptions};
+
+    fn ply() -> PLYFile {
+        let mut ply = PLYFile::new(Format::BinaryLittleEndian {
+            version: "1.0".to_string(),
+        });
+        let mut props = Property::new();
+        props.push_prop("x", PLYValueTypeName::Float);
+        let mut element = GenericElement::new(props);
+        for i in 0..1000 {
+            element
+                .push_payload(Payload::new(vec![(i as f32).into()]))
+                .unwrap();
+        }
+        ply.elements.push(Element::Element {
+            name: "vertex".to_string(),
+            elements: element,
+        });
+        ply
+    }
+
+    /// Write `ply` to temporary file and read it back by path
+    fn round_trip(compression: Compression) -> (Vec<u8>, Result<PLYFile, PLYError>) {
+        let mut writer = BufWriter::new(Vec::new());
+        let options = WriteOptions {
+            compression,
+            ..WriteOptions::default()
+        };
+        ply().write_with_options(&mut writer, &options).unwrap();
+        let bytes = writer.into_inner().unwrap();
+
+        let path = std::env::temp_dir().join(format!(
+            "ply_compression_{:?}_{}.ply",
+            compression,
+            std::process::id()
+        ));
+        std::fs::write(&path, &bytes).unwrap();
+        let read = PLYFile::from_file(&path);
+        if let Ok(read) = &read {
+            let header = PLYHeader::from_file(&path).unwrap();
+            assert_eq!(header.file.elements[0].count(), read.elements[0].count());
+        }
+        std::fs::remove_file(path).unwrap();
+        (bytes, read)
+    }
+
+    #[test]
+    fn test_detect_compression() {
+        assert_eq!(Compression::detect(b"ply\n"), Compression::None);
+        assert_eq!(Compression::detect(&[0x1f, 0x8b, 8]), Compression::Gzip);
+        assert_eq!(
+            Compression::detect(&[0x28, 0xb5, 0x2f, 0xfd]),
+            Compression::Zstd
+        );
+        assert_eq!(Compression::detect(&[]), Compression::None);
+    }
+
+    #[test]
+    fn test_plain_file() {
+        let (_, read) = round_trip(Compression::None);
+        assert_eq!(read.unwrap(), ply());
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_file() {
+        let (bytes, read) = round_trip(Compression::Gzip);
+        assert_eq!(Compression::detect(&bytes), Compression::Gzip);
+        assert_eq!(read.unwrap(), ply());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_file() {
+        let (bytes, read) = round_trip(Compression::Zstd);
+        assert_eq!(Compression::detect(&bytes), Compression::Zstd);
+        assert_eq!(read.unwrap(), ply());
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn test_gzip_not_enabled() {
+        let mut writer = BufWriter::new(Vec::new());
+        let options = WriteOptions {
+            compression: Compression::Gzip,
+            ..WriteOptions::default()
+        };
+        let result = ply().write_with_options(&mut writer, &options);
+        assert_eq!(
+            result.map_err(|e| e.kind()),
+            Err(std::io::ErrorKind::Unsupported)
+        );
+
+        let gzip_magic: &[u8] = &[0x1f, 0x8b, 8, 0];
+        let path = std::env::temp_dir().join(format!("ply_gzip_{}.ply", std::process::id()));
+        std::fs::write(&path, gzip_magic).unwrap();
+        let result = PLYFile::from_file(&path);
+        std::fs::remove_file(path).unwrap();
+        assert!(matches!(result, Err(PLYError::Unsupported(_))));
+    }
+}
@@ -0,0 +1,150 @@
+use std::io::{BufRead, Write};
+
+use crate::{error::PLYResult, Format, PLYFile, PLYReader, PLYWriter, WriteOptions};
+
+impl PLYFile {
+    /// Change encoding of payload, written in `format` by the next [PLYFile::write]
+    ///
+    /// Values are kept as they are, so converting between ascii and binary is lossless
+    /// with the default [AsciiOptions](crate::AsciiOptions).
+    pub fn convert_to(&mut self, format: Format) {
+        self.format = format;
+    }
+}
+
+/// Read PLY file from `reader` and write it to `writer` in `format`, returns `writer`
+///
+/// Rows are converted one at a time by [PLYReader] and [PLYWriter], so memory usage does not depend on
+/// the number of rows. Elements, properties and their type names are kept in order.
+/// Comments and obj_info lines are kept in their order, and written before elements.
+/// Input is not decompressed, wrap `reader` in a decoder for compressed files.
+///
+/// No [Limits](crate::Limits) are applied to input. For input which is not trusted,
+/// read it by [PLYReader::with_limits] and write rows by [PLYWriter] instead.
+///
+/// ```no_run
+/// # fn main() -> ply::error::PLYResult<()> {
+/// use std::{fs::File, io::{BufReader, BufWriter}};
+/// use ply::{Format, WriteOptions};
+///
+/// let reader = BufReader::new(File::open("mesh_ascii.ply")?);
+/// let writer = BufWriter::new(File::create("mesh.ply")?);
+/// let format = Format::BinaryLittleEndian { version: "1.0".to_string() };
+/// ply::convert(reader, writer, format, &WriteOptions::default())?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn convert<R: BufRead, W: Write>(
+    reader: R,
+    writer: W,
+    format: Format,
+    options: &WriteOptions,
+) -> PLYResult<W> {
+    let mut reader = PLYReader::new(reader)?;
+    let mut schema = reader.header().clone();
+    schema.convert_to(format);
+    let mut writer = PLYWriter::with_options(writer, &schema, options)?;
+    while let Some(element) = reader.next_element()? {
+        let mut rows = writer.element(element.name())?;
+        for row in element {
+            rows.write_row(&row?)?;
+        }
+    }
+    writer.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::BufWriter;
+
+    use super::convert;
+    use crate::{Format, PLYFile, WriteOptions};
+
+    const ASCII: &str = "\
+ply
+format ascii 1.0
+comment made by hand
+obj_info num_cols 2
+element vertex 3
+property float32 x
+property float32 y
+element edge 0
+property int vertex1
+element face 2
+property list uchar int vertex_indices
+property uchar red
+end_header
+0 0.5
+1 -1.25
+2 3e-3
+3 0 1 2 255
+2 1 2 0
+";
+
+    #[test]
+    fn test_convert() {
+        let ascii = PLYFile::from_bytes(ASCII.as_bytes()).unwrap();
+        for format in [
+            Format::BinaryLittleEndian {
+                version: "1.0".to_string(),
+            },
+            Format::BinaryBigEndian {
+                version: "1.0".to_string(),
+            },
+        ] {
+            let binary = convert(
+                ASCII.as_bytes(),
+                Vec::new(),
+                format.clone(),
+                &WriteOptions::default(),
+            )
+            .unwrap();
+
+            let mut expected = ascii.clone();
+            expected.convert_to(format);
+            let mut writer = BufWriter::new(Vec::new());
+            expected.write(&mut writer).unwrap();
+            assert_eq!(binary, writer.into_inner().unwrap());
+            assert_eq!(PLYFile::from_bytes(&binary).unwrap(), expected);
+
+            let back = convert(
+                binary.as_slice(),
+                Vec::new(),
+                ascii.format.clone(),
+                &WriteOptions::default(),
+            )
+            .unwrap();
+            assert_eq!(PLYFile::from_bytes(&back).unwrap(), ascii);
+        }
+    }
+
+    #[test]
+    fn test_convert_header_order() {
+        let ascii = "\
+ply
+format ascii 1.0
+obj_info num_cols 2
+comment made by hand
+obj_info num_rows 1
+comment for test
+comment of order
+element vertex 0
+property float x
+end_header
+";
+        let format = Format::BinaryLittleEndian {
+            version: "1.0".to_string(),
+        };
+        let binary = convert(
+            ascii.as_bytes(),
+            Vec::new(),
+            format,
+            &WriteOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(binary).unwrap(),
+            ascii.replace("format ascii", "format binary_little_endian")
+        );
+    }
+}
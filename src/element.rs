@@ -1,4 +1,4 @@
-use crate::{Payload, Property, PropertyList};
+use crate::{MixedPayload, MixedProperty, Payload, Property, PropertyList};
 
 pub use self::generic_element::GenericElement;
 
@@ -17,6 +17,12 @@ pub enum Element {
         name: String,
         elements: GenericElement<PropertyList>,
     },
+
+    /// Element with both scalar and list properties, or with several list properties
+    MixedElement {
+        name: String,
+        elements: GenericElement<MixedProperty, MixedPayload>,
+    },
 }
 
 /// Trait indicate type can be a [PlyElement]
@@ -29,3 +35,50 @@ pub trait IntoPlyElement {
     /// convert to [Payload]
     fn into_payload(self) -> Payload;
 }
+
+impl Element {
+    /// Returns element name
+    pub fn name(&self) -> &str {
+        match self {
+            Element::Element { name, .. }
+            | Element::ListElement { name, .. }
+            | Element::MixedElement { name, .. } => name,
+        }
+    }
+
+    /// Returns number of rows
+    pub fn count(&self) -> usize {
+        match self {
+            Element::Element { elements, .. } => elements.count(),
+            Element::ListElement { elements, .. } => elements.count(),
+            Element::MixedElement { elements, .. } => elements.count(),
+        }
+    }
+
+    /// Returns number of rows in payload, less than [Element::count] until payload is read
+    pub(crate) fn payload_len(&self) -> usize {
+        match self {
+            Element::Element { elements, .. } => elements.payloads.len(),
+            Element::ListElement { elements, .. } => elements.payloads.len(),
+            Element::MixedElement { elements, .. } => elements.payloads.len(),
+        }
+    }
+
+    /// Keep only the first `rows` rows, count is set to `rows`
+    pub(crate) fn truncate(&mut self, rows: usize) {
+        match self {
+            Element::Element { elements, .. } => elements.truncate(rows),
+            Element::ListElement { elements, .. } => elements.truncate(rows),
+            Element::MixedElement { elements, .. } => elements.truncate(rows),
+        }
+    }
+
+    /// Returns properties of element as [MixedProperty], whichever variant it is
+    pub fn to_mixed_property(&self) -> MixedProperty {
+        match self {
+            Element::Element { elements, .. } => elements.props.clone().into(),
+            Element::ListElement { elements, .. } => elements.props.clone().into(),
+            Element::MixedElement { elements, .. } => elements.props.clone(),
+        }
+    }
+}
@@ -1,20 +1,22 @@
 use crate::{
     error::{PLYError, PLYResult},
-    IntoPlyElement, Payload, Property, PropertyList,
+    IntoPlyElement, MixedPayload, MixedProperty, MixedValue, PLYValue, Payload, Property,
+    PropertyList,
 };
 
 #[derive(Debug, Clone, PartialEq)]
 /// Struct represent Generic PLY Element
 ///
-/// Note: generic type P expect [Property] or [PropertyList].
-pub struct GenericElement<P> {
+/// Note: generic type P expect [Property], [PropertyList] or [MixedProperty],
+/// T is [MixedPayload] for [MixedProperty], [Payload] otherwise.
+pub struct GenericElement<P, T = Payload> {
     pub(crate) count: usize,
     pub(crate) props: P,
-    pub(crate) payloads: Vec<Payload>,
+    pub(crate) payloads: Vec<T>,
 }
 
-impl<P> GenericElement<P> {
-    pub fn new(property: P) -> GenericElement<P> {
+impl<P, T> GenericElement<P, T> {
+    pub fn new(property: P) -> GenericElement<P, T> {
         Self {
             count: 0,
             props: property,
@@ -22,6 +24,15 @@ impl<P> GenericElement<P> {
         }
     }
 
+    /// Element of `count` rows without payload, like schema of [PLYWriter](crate::PLYWriter)
+    pub fn with_count(property: P, count: usize) -> GenericElement<P, T> {
+        Self {
+            count,
+            props: property,
+            payloads: Vec::new(),
+        }
+    }
+
     pub fn into_ploperty(self) -> P {
         self.props
     }
@@ -34,21 +45,26 @@ impl<P> GenericElement<P> {
         &mut self.props
     }
 
-    pub fn into_payload(self) -> Vec<Payload> {
+    pub fn into_payload(self) -> Vec<T> {
         self.payloads
     }
 
-    pub fn payload(&self) -> &[Payload] {
+    pub fn payload(&self) -> &[T] {
         &self.payloads
     }
 
-    pub fn payload_mut(&mut self) -> &mut [Payload] {
+    pub fn payload_mut(&mut self) -> &mut [T] {
         &mut self.payloads
     }
 
     pub fn count(&self) -> usize {
         self.count
     }
+
+    pub(crate) fn truncate(&mut self, rows: usize) {
+        self.payloads.truncate(rows);
+        self.count = rows.min(self.count);
+    }
 }
 
 impl GenericElement<Property> {
@@ -85,6 +101,71 @@ impl GenericElement<PropertyList> {
     }
 }
 
+impl GenericElement<MixedProperty, MixedPayload> {
+    pub fn push_payload(&mut self, payload: MixedPayload) -> PLYResult<()> {
+        self.property().check_payload(&payload)?;
+
+        self.count += 1;
+        self.payloads.push(payload);
+        Ok(())
+    }
+
+    /// Iterator over values of scalar property `name` in each row
+    ///
+    /// Returns None if element has no scalar property named `name`.
+    pub fn scalar_values(&self, name: &str) -> Option<impl Iterator<Item = PLYValue> + '_> {
+        let i = self
+            .property()
+            .iter()
+            .position(|(n, kind)| n == name && !kind.is_list())?;
+        Some(self.payloads.iter().filter_map(move |p| p[i].as_scalar()))
+    }
+
+    /// Iterator over values of list property `name` in each row
+    ///
+    /// Returns None if element has no list property named `name`.
+    pub fn list_values(&self, name: &str) -> Option<impl Iterator<Item = &[PLYValue]> + '_> {
+        let i = self
+            .property()
+            .iter()
+            .position(|(n, kind)| n == name && kind.is_list())?;
+        Some(self.payloads.iter().filter_map(move |p| p[i].as_list()))
+    }
+}
+
+impl From<GenericElement<Property>> for GenericElement<MixedProperty, MixedPayload> {
+    fn from(e: GenericElement<Property>) -> Self {
+        Self {
+            count: e.count,
+            props: e.props.into(),
+            payloads: e
+                .payloads
+                .into_iter()
+                .map(|p| {
+                    p.into_iter()
+                        .map(MixedValue::Scalar)
+                        .collect::<Vec<_>>()
+                        .into()
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<GenericElement<PropertyList>> for GenericElement<MixedProperty, MixedPayload> {
+    fn from(e: GenericElement<PropertyList>) -> Self {
+        Self {
+            count: e.count,
+            props: e.props.into(),
+            payloads: e
+                .payloads
+                .into_iter()
+                .map(|p| MixedPayload(vec![MixedValue::List(p.0)]))
+                .collect(),
+        }
+    }
+}
+
 impl<Item: IntoPlyElement> std::iter::FromIterator<Item> for GenericElement<Property> {
     fn from_iter<T: IntoIterator<Item = Item>>(iter: T) -> Self {
         iter.into_iter().map(|x| x.into_payload()).fold(
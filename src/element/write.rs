@@ -1,18 +1,30 @@
 use std::io::Write;
 
-use crate::{writer::header::PlyWriteHeader, Element};
+use crate::{
+    writer::{header::PlyWriteHeader, WriteOptions},
+    Element,
+};
 
 impl<T: Write> PlyWriteHeader<T> for Element {
     fn write_header(&self, writer: &mut T) -> std::io::Result<()> {
+        self.write_header_with(writer, &WriteOptions::default())
+    }
+
+    fn write_header_with(&self, writer: &mut T, options: &WriteOptions) -> std::io::Result<()> {
         match self {
             Element::Element { name, elements } => {
                 writeln!(writer, "element {} {}", name, elements.count())?;
-                elements.property().write_header(writer)
+                elements.property().write_header_with(writer, options)
             }
 
             Element::ListElement { name, elements } => {
                 writeln!(writer, "element {} {}", name, elements.count())?;
-                elements.property().write_header(writer)
+                elements.property().write_header_with(writer, options)
+            }
+
+            Element::MixedElement { name, elements } => {
+                writeln!(writer, "element {} {}", name, elements.count())?;
+                elements.property().write_header_with(writer, options)
             }
         }
     }
@@ -49,6 +61,7 @@ mod test {
                         "green".to_string(),
                         "blue".to_string(),
                     ],
+                    namings: vec![TypeNaming::Classic; 6],
                 },
                 payloads: Vec::<Payload>::with_capacity(20),
             },
@@ -1,7 +1,9 @@
 //! Error type definitions
 
+use std::{fmt::Display, io};
+
 pub type PLYResult<T> = Result<T, PLYError>;
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug)]
 /// PLY Error type
 pub enum PLYError {
     MissmatchDataType,
@@ -10,4 +12,381 @@ pub enum PLYError {
     TryIntoUsizeEr,
     TypeConversionFail,
     PropertyLengthErr,
+    /// I/O error of underlying reader or writer
+    Io(io::Error),
+    /// Header line is malformed or not allowed at the position
+    ///
+    /// `line` is 1-based line number, `text` is the offending line.
+    HeaderSyntax {
+        line: usize,
+        text: String,
+        reason: String,
+    },
+    /// Payload row is malformed
+    ///
+    /// `row` is 0-based row index in the element, `offset` is byte offset from start of file.
+    Payload {
+        element: String,
+        row: usize,
+        offset: usize,
+        reason: String,
+    },
+    /// Input ended before all rows declared in header were read
+    UnexpectedEof {
+        element: String,
+        row: usize,
+        offset: usize,
+    },
+    /// Operation is not supported for the file, like binary view over ascii file
+    Unsupported(String),
+    /// Data after the last row of the last element (strict mode)
+    TrailingData {
+        offset: usize,
+    },
+    /// Element is written out of order declared in header
+    ///
+    /// `expected` is the next element to write, or None after the last element.
+    ElementOrder {
+        element: String,
+        expected: Option<String>,
+    },
+    /// Number of rows written differs from count of element declared in header
+    RowCount {
+        element: String,
+        declared: usize,
+        written: usize,
+    },
+    /// Input exceeds [Limits](crate::Limits) of [ReadOptions](crate::ReadOptions)
+    ///
+    /// `found` is the value which exceeds `max`, like declared count of rows or length of list.
+    /// For [Limit::HeaderBytes] it is the number of bytes read until the first one over `max`.
+    LimitExceeded {
+        limit: Limit,
+        max: usize,
+        found: usize,
+    },
+    /// File cannot be written consistently, found by [PLYFile::validate](crate::PLYFile::validate)
+    ///
+    /// `row` is 0-based row index in the element if a row of payload is invalid.
+    Invalid {
+        element: String,
+        row: Option<usize>,
+        reason: String,
+    },
+}
+
+/// Limit of [PLYError::LimitExceeded], by field of [Limits](crate::Limits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    /// [Limits::max_elements](crate::Limits::max_elements)
+    Elements,
+    /// [Limits::max_rows](crate::Limits::max_rows)
+    Rows,
+    /// [Limits::max_list_len](crate::Limits::max_list_len)
+    ListLength,
+    /// [Limits::max_decoded_bytes](crate::Limits::max_decoded_bytes)
+    DecodedBytes,
+    /// [Limits::max_header_lines](crate::Limits::max_header_lines)
+    HeaderLines,
+    /// [Limits::max_header_bytes](crate::Limits::max_header_bytes)
+    HeaderBytes,
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Limit::Elements => write!(f, "number of elements"),
+            Limit::Rows => write!(f, "rows of element"),
+            Limit::ListLength => write!(f, "list length"),
+            Limit::DecodedBytes => write!(f, "decoded bytes"),
+            Limit::HeaderLines => write!(f, "header lines"),
+            Limit::HeaderBytes => write!(f, "header bytes"),
+        }
+    }
+}
+
+impl PLYError {
+    pub(crate) fn header_syntax<T: Into<String>, S: Into<String>>(
+        line: usize,
+        text: T,
+        reason: S,
+    ) -> PLYError {
+        PLYError::HeaderSyntax {
+            line,
+            text: text.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for PLYError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PLYError::MissmatchDataType => write!(f, "data type mismatch"),
+            PLYError::UnknownPLYTypeIdentifier => write!(f, "unknown PLY type identifier"),
+            PLYError::ParseFromStrErr => write!(f, "failed to parse value from str"),
+            PLYError::TryIntoUsizeEr => write!(f, "failed to convert value into usize"),
+            PLYError::TypeConversionFail => write!(f, "type conversion failed"),
+            PLYError::PropertyLengthErr => write!(f, "property length mismatch"),
+            PLYError::Io(e) => write!(f, "I/O error: {e}"),
+            PLYError::HeaderSyntax { line, text, reason } => {
+                write!(f, "header line {line}: {reason}: \"{text}\"")
+            }
+            PLYError::Payload {
+                element,
+                row,
+                offset,
+                reason,
+            } => write!(
+                f,
+                "element \"{element}\" row {row} (byte {offset}): {reason}"
+            ),
+            PLYError::UnexpectedEof {
+                element,
+                row,
+                offset,
+            } => write!(
+                f,
+                "unexpected end of file at element \"{element}\" row {row} (byte {offset})"
+            ),
+            PLYError::Unsupported(reason) => write!(f, "unsupported: {reason}"),
+            PLYError::TrailingData { offset } => {
+                write!(f, "unexpected data after the last element (byte {offset})")
+            }
+            PLYError::ElementOrder {
+                element,
+                expected: Some(expected),
+            } => write!(
+                f,
+                "element \"{element}\" written out of order, expected \"{expected}\""
+            ),
+            PLYError::ElementOrder {
+                element,
+                expected: None,
+            } => write!(f, "element \"{element}\" written after the last element"),
+            PLYError::RowCount {
+                element,
+                declared,
+                written,
+            } => write!(
+                f,
+                "element \"{element}\" declares {declared} rows, but {written} rows written"
+            ),
+            PLYError::LimitExceeded { limit, max, found } => {
+                write!(f, "limit of {limit} exceeded: {found} > {max}")
+            }
+            PLYError::Invalid {
+                element,
+                row: Some(row),
+                reason,
+            } => write!(f, "element \"{element}\" row {row}: {reason}"),
+            PLYError::Invalid {
+                element,
+                row: None,
+                reason,
+            } => write!(f, "element \"{element}\": {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for PLYError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PLYError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PLYError {
+    fn from(e: io::Error) -> Self {
+        PLYError::Io(e)
+    }
+}
+
+/// [io::Error] does not implement [PartialEq], so I/O errors are compared by [io::ErrorKind].
+impl PartialEq for PLYError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (PLYError::Io(a), PLYError::Io(b)) => a.kind() == b.kind(),
+            (
+                PLYError::HeaderSyntax {
+                    line: l1,
+                    text: t1,
+                    reason: r1,
+                },
+                PLYError::HeaderSyntax {
+                    line: l2,
+                    text: t2,
+                    reason: r2,
+                },
+            ) => l1 == l2 && t1 == t2 && r1 == r2,
+            (
+                PLYError::Payload {
+                    element: e1,
+                    row: r1,
+                    offset: o1,
+                    reason: s1,
+                },
+                PLYError::Payload {
+                    element: e2,
+                    row: r2,
+                    offset: o2,
+                    reason: s2,
+                },
+            ) => e1 == e2 && r1 == r2 && o1 == o2 && s1 == s2,
+            (
+                PLYError::UnexpectedEof {
+                    element: e1,
+                    row: r1,
+                    offset: o1,
+                },
+                PLYError::UnexpectedEof {
+                    element: e2,
+                    row: r2,
+                    offset: o2,
+                },
+            ) => e1 == e2 && r1 == r2 && o1 == o2,
+            (PLYError::Unsupported(a), PLYError::Unsupported(b)) => a == b,
+            (PLYError::TrailingData { offset: a }, PLYError::TrailingData { offset: b }) => a == b,
+            (
+                PLYError::ElementOrder {
+                    element: e1,
+                    expected: x1,
+                },
+                PLYError::ElementOrder {
+                    element: e2,
+                    expected: x2,
+                },
+            ) => e1 == e2 && x1 == x2,
+            (
+                PLYError::RowCount {
+                    element: e1,
+                    declared: d1,
+                    written: w1,
+                },
+                PLYError::RowCount {
+                    element: e2,
+                    declared: d2,
+                    written: w2,
+                },
+            ) => e1 == e2 && d1 == d2 && w1 == w2,
+            (
+                PLYError::LimitExceeded {
+                    limit: l1,
+                    max: m1,
+                    found: f1,
+                },
+                PLYError::LimitExceeded {
+                    limit: l2,
+                    max: m2,
+                    found: f2,
+                },
+            ) => l1 == l2 && m1 == m2 && f1 == f2,
+            (
+                PLYError::Invalid {
+                    element: e1,
+                    row: r1,
+                    reason: s1,
+                },
+                PLYError::Invalid {
+                    element: e2,
+                    row: r2,
+                    reason: s2,
+                },
+            ) => e1 == e2 && r1 == r2 && s1 == s2,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+/// Input which is not well-formed, but is accepted in lenient [ParseMode](crate::ParseMode)
+///
+/// Strict mode rejects it with the [PLYError] converted by [From].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PLYWarning {
+    /// Header line starting with unknown keyword was ignored, `line` is 1-based
+    UnknownKeyword { line: usize, keyword: String },
+    /// Empty header line was ignored
+    EmptyHeaderLine { line: usize },
+    /// `words` after complete header line were ignored, like `junk` of `element vertex 8 junk`
+    ExtraWords { line: usize, words: String },
+    /// Format version is not `1.0`, file was read as version `1.0`
+    UnsupportedVersion { version: String },
+    /// Data after the last row of the last element was ignored
+    TrailingData { offset: usize },
+    /// Input ended at `row` of element, `missing` rows were dropped from its count
+    ///
+    /// Reported by [ReadOptions::recover_truncated](crate::ReadOptions::recover_truncated)
+    /// for the truncated element, and for every element after it with `row` 0.
+    MissingRows {
+        element: String,
+        row: usize,
+        offset: usize,
+        missing: usize,
+    },
+}
+
+impl Display for PLYWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PLYWarning::UnknownKeyword { line, keyword } => {
+                write!(
+                    f,
+                    "header line {line}: unknown keyword \"{keyword}\" ignored"
+                )
+            }
+            PLYWarning::EmptyHeaderLine { line } => {
+                write!(f, "header line {line}: empty line ignored")
+            }
+            PLYWarning::ExtraWords { line, words } => {
+                write!(f, "header line {line}: extra words \"{words}\" ignored")
+            }
+            PLYWarning::UnsupportedVersion { version } => {
+                write!(f, "unsupported version {version}, read as 1.0")
+            }
+            PLYWarning::TrailingData { offset } => {
+                write!(f, "data after the last element ignored (byte {offset})")
+            }
+            PLYWarning::MissingRows {
+                element,
+                row,
+                offset,
+                missing,
+            } => write!(
+                f,
+                "element \"{element}\" truncated at row {row} (byte {offset}): {missing} rows missing"
+            ),
+        }
+    }
+}
+
+impl From<PLYWarning> for PLYError {
+    fn from(warning: PLYWarning) -> Self {
+        match warning {
+            PLYWarning::UnknownKeyword { line, keyword } => {
+                PLYError::header_syntax(line, keyword, "unknown keyword")
+            }
+            PLYWarning::EmptyHeaderLine { line } => {
+                PLYError::header_syntax(line, "", "empty line is not allowed")
+            }
+            PLYWarning::ExtraWords { line, words } => {
+                PLYError::header_syntax(line, words, "extra words after complete line")
+            }
+            PLYWarning::UnsupportedVersion { version } => {
+                PLYError::header_syntax(2, version, "unsupported version, expected 1.0")
+            }
+            PLYWarning::TrailingData { offset } => PLYError::TrailingData { offset },
+            PLYWarning::MissingRows {
+                element,
+                row,
+                offset,
+                ..
+            } => PLYError::UnexpectedEof {
+                element,
+                row,
+                offset,
+            },
+        }
+    }
 }
@@ -1,8 +1,9 @@
 use std::io::{BufWriter, Write};
 
 use crate::{
-    writer::{header::PlyWriteHeader, payload::write_element_payload},
-    Comment, Element, Format,
+    compression::Compressed,
+    writer::{header::PlyWriteHeader, payload::write_element_payload, WriteOptions},
+    Comment, Compression, Element, Format, ObjInfo,
 };
 
 pub const MAGIC_NUMBER: &str = "ply";
@@ -13,6 +14,11 @@ pub const END_HEADER: &str = "end_header";
 pub struct PLYFile {
     pub format: Format,
     pub comments: Vec<Comment>,
+    pub obj_infos: Vec<ObjInfo>,
+    /// Number of comments before each of `obj_infos` in header, to keep order of the lines as read
+    ///
+    /// obj_info lines without position are written after all comments.
+    pub obj_info_positions: Vec<usize>,
     pub elements: Vec<Element>,
 }
 
@@ -21,19 +27,46 @@ impl PLYFile {
         Self {
             format,
             comments: Vec::new(),
+            obj_infos: Vec::new(),
+            obj_info_positions: Vec::new(),
             elements: Vec::new(),
         }
     }
 
     pub fn write<T: Write>(&self, writer: &mut BufWriter<T>) -> std::io::Result<()> {
-        self.write_header(writer)?;
+        self.write_with_options(writer, &WriteOptions::default())
+    }
+
+    /// Write PLY file, compressed if [WriteOptions::compression] is set.
+    pub fn write_with_options<T: Write>(
+        &self,
+        writer: &mut BufWriter<T>,
+        options: &WriteOptions,
+    ) -> std::io::Result<()> {
+        if options.compression == Compression::None {
+            return self.write_to(writer, options);
+        }
+        let mut compressed = BufWriter::new(Compressed::new(writer, options.compression)?);
+        self.write_to(&mut compressed, options)?;
+        compressed
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .finish()?
+            .flush()
+    }
+
+    fn write_to<T: Write>(&self, writer: &mut T, options: &WriteOptions) -> std::io::Result<()> {
+        self.write_header_with(writer, options)?;
         for element in self.elements.iter() {
             match element {
                 Element::Element { elements: e, .. } => {
-                    write_element_payload(e, writer, &self.format)?
+                    write_element_payload(e, writer, &self.format, &options.ascii)?
                 }
                 Element::ListElement { elements: e, .. } => {
-                    write_element_payload(e, writer, &self.format)?
+                    write_element_payload(e, writer, &self.format, &options.ascii)?
+                }
+                Element::MixedElement { elements: e, .. } => {
+                    write_element_payload(e, writer, &self.format, &options.ascii)?
                 }
             };
         }
@@ -43,13 +76,28 @@ impl PLYFile {
 
 impl<T: Write> PlyWriteHeader<T> for PLYFile {
     fn write_header(&self, writer: &mut T) -> std::io::Result<()> {
+        self.write_header_with(writer, &WriteOptions::default())
+    }
+
+    fn write_header_with(&self, writer: &mut T, options: &WriteOptions) -> std::io::Result<()> {
         writeln!(writer, "{MAGIC_NUMBER}")?;
-        self.format.write_header(writer)?;
-        for comment in self.comments.iter() {
-            comment.write_header(writer)?;
+        self.format.write_header_with(writer, options)?;
+        let mut comments = self.comments.iter();
+        let mut written = 0;
+        for (i, obj_info) in self.obj_infos.iter().enumerate() {
+            let position = self.obj_info_positions.get(i).copied();
+            let before = position.unwrap_or(usize::MAX).saturating_sub(written);
+            for comment in comments.by_ref().take(before) {
+                comment.write_header_with(writer, options)?;
+                written += 1;
+            }
+            obj_info.write_header_with(writer, options)?;
+        }
+        for comment in comments {
+            comment.write_header_with(writer, options)?;
         }
         for element in self.elements.iter() {
-            element.write_header(writer)?
+            element.write_header_with(writer, options)?
         }
         writeln!(writer, "{END_HEADER}")?;
         Ok(())
@@ -71,6 +119,7 @@ fn test_write_ply() {
                         PLYValueTypeName::Float,
                     ],
                     names: vec!["x".to_string(), "y".to_string(), "z".to_string()],
+                    namings: vec![TypeNaming::Classic; 3],
                 },
                 payloads: vec![
                     Payload::new(vec![
@@ -124,6 +173,8 @@ fn test_write_ply() {
                     count: PLYValueTypeName::Uchar,
                     prop: PLYValueTypeName::Char,
                     name: "vertex_id".to_string(),
+                    count_naming: TypeNaming::Classic,
+                    naming: TypeNaming::Classic,
                 },
                 payloads: vec![
                     Payload::new(vec![PLYValue::Char(3)]),
@@ -141,6 +192,8 @@ fn test_write_ply() {
                 version: "1.0".to_string(),
             },
             comments: vec![Comment::new(vec!["test".to_string(), "data".to_string()])],
+            obj_infos: Vec::new(),
+            obj_info_positions: Vec::new(),
             elements: vec![element_vertex, element_list],
         }
     };
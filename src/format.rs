@@ -10,6 +10,16 @@ pub enum Format {
     BinaryLittleEndian { version: String },
 }
 
+impl Format {
+    pub fn version(&self) -> &str {
+        match self {
+            Format::Ascii { version }
+            | Format::BinaryBigEndian { version }
+            | Format::BinaryLittleEndian { version } => version,
+        }
+    }
+}
+
 impl Display for Format {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
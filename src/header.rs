@@ -0,0 +1,14 @@
+use crate::PLYFile;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Header of PLY file, read without payload
+///
+/// See [PLYHeader::from_file] and [PLYHeader::from_buf_read].
+pub struct PLYHeader {
+    /// Format, comments, obj_info and elements declared in header.
+    ///
+    /// Elements have counts and properties, but no payload.
+    pub file: PLYFile,
+    /// Byte offset from start of file where payload starts
+    pub payload_offset: usize,
+}
@@ -6,14 +6,37 @@
 pub mod error;
 
 pub(crate) mod ply_value;
-pub use ply_value::{PLYValue, PLYValueTypeName};
+pub use ply_value::{PLYValue, PLYValueTypeName, TypeNaming};
 
 pub(crate) mod reader;
+#[cfg(feature = "async")]
+pub use reader::asynchronous::AsyncPLYReader;
+pub use reader::stream::{ElementReader, PLYReader};
+pub use reader::{limits::Limits, ParseMode, ReadOptions};
 pub(crate) mod writer;
+pub use writer::stream::{ElementWriter, PLYWriter};
+pub use writer::{
+    ascii::{AsciiOptions, FloatFormat},
+    WriteOptions,
+};
 
 mod file;
 pub use file::*;
 
+mod convert;
+pub use convert::*;
+
+mod header;
+pub use header::*;
+
+mod compression;
+pub use compression::*;
+
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "mmap")]
+pub use mmap::*;
+
 mod format;
 pub use format::*;
 
@@ -28,3 +51,6 @@ pub use payload::*;
 
 mod comment;
 pub use comment::*;
+
+mod obj_info;
+pub use obj_info::*;
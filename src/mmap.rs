@@ -0,0 +1,532 @@
+//! Zero-copy view over binary PLY file (feature `mmap`)
+
+use std::{convert::TryInto, fs::File, marker::PhantomData, ops::Deref, path::Path};
+
+use memmap2::Mmap;
+
+use crate::{
+    error::{PLYError, PLYResult},
+    Format, MixedProperty, PLYHeader, PLYValue, PLYValueTypeName, Payload, PropertyKind,
+};
+
+/// Read-only view over binary PLY bytes, usually a memory mapped file
+///
+/// Only header is parsed on creation. Elements without list properties can be accessed
+/// by row or by column through [ElementView], values are decoded from the bytes on access.
+pub struct PLYView<D> {
+    data: D,
+    header: PLYHeader,
+    big_endian: bool,
+    layouts: Vec<ElementLayout>,
+}
+
+/// Byte range and properties of an element in payload
+struct ElementLayout {
+    start: usize,
+    end: usize,
+    props: MixedProperty,
+}
+
+impl PLYView<Mmap> {
+    /// Map file at `path` into memory and read its header.
+    ///
+    /// # Safety
+    ///
+    /// File must not be modified or truncated while it is mapped, see [Mmap::map].
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> PLYResult<Self> {
+        let file = File::open(path)?;
+        PLYView::new(Mmap::map(&file)?)
+    }
+}
+
+impl<D: Deref<Target = [u8]>> PLYView<D> {
+    /// Read header of binary PLY in `data` and locate payload of each element.
+    ///
+    /// Payload of elements without list properties is located from the row size,
+    /// elements with list properties are walked through by their list lengths.
+    pub fn new(data: D) -> PLYResult<Self> {
+        let header = PLYHeader::from_buf_read(&mut &data[..])?;
+        let big_endian = match header.file.format {
+            Format::Ascii { .. } => {
+                return Err(PLYError::Unsupported("view over ascii payload".to_string()))
+            }
+            Format::BinaryBigEndian { .. } => true,
+            Format::BinaryLittleEndian { .. } => false,
+        };
+        let mut layouts = Vec::with_capacity(header.file.elements.len());
+        let mut start = header.payload_offset;
+        for element in &header.file.elements {
+            let props = element.to_mixed_property();
+            let end = element_end(
+                &data,
+                start,
+                element.name(),
+                element.count(),
+                &props,
+                big_endian,
+            )?;
+            layouts.push(ElementLayout { start, end, props });
+            start = end;
+        }
+        Ok(Self {
+            data,
+            header,
+            big_endian,
+            layouts,
+        })
+    }
+
+    pub fn header(&self) -> &PLYHeader {
+        &self.header
+    }
+
+    /// Returns view of element `name`
+    ///
+    /// Returns None if there is no such element, or element has list property.
+    pub fn element(&self, name: &str) -> Option<ElementView<'_>> {
+        let index = self
+            .header
+            .file
+            .elements
+            .iter()
+            .position(|e| e.name() == name)?;
+        let layout = &self.layouts[index];
+        let mut columns = Vec::with_capacity(layout.props.len());
+        let mut stride = 0;
+        for (name, kind) in layout.props.iter() {
+            match kind {
+                PropertyKind::Scalar(ty) => {
+                    columns.push(ColumnLayout {
+                        name,
+                        ty,
+                        offset: stride,
+                    });
+                    stride += ty.bytes_len();
+                }
+                PropertyKind::List { .. } => return None,
+            }
+        }
+        Some(ElementView {
+            bytes: &self.data[layout.start..layout.end],
+            count: self.header.file.elements[index].count(),
+            stride,
+            columns,
+            big_endian: self.big_endian,
+        })
+    }
+}
+
+/// Returns byte offset where payload of element starting at `start` ends
+fn element_end(
+    data: &[u8],
+    start: usize,
+    name: &str,
+    count: usize,
+    props: &MixedProperty,
+    big_endian: bool,
+) -> PLYResult<usize> {
+    let eof = |row| PLYError::UnexpectedEof {
+        element: name.to_string(),
+        row,
+        offset: data.len(),
+    };
+    let available = data.len() - start;
+
+    let fixed_stride = props.iter().try_fold(0, |stride, (_, kind)| match kind {
+        PropertyKind::Scalar(ty) => Some(stride + ty.bytes_len()),
+        PropertyKind::List { .. } => None,
+    });
+    if let Some(stride) = fixed_stride {
+        return match count.checked_mul(stride) {
+            Some(len) if len <= available => Ok(start + len),
+            _ => Err(eof(available / stride)),
+        };
+    }
+
+    let mut offset = start;
+    for row in 0..count {
+        for (_, kind) in props.iter() {
+            match kind {
+                PropertyKind::Scalar(ty) => offset += ty.bytes_len(),
+                PropertyKind::List { count, prop } => {
+                    let len = data
+                        .get(offset..)
+                        .and_then(|bytes| decode(count, bytes, big_endian))
+                        .ok_or_else(|| eof(row))?;
+                    let len: usize = len.try_into().map_err(|_| PLYError::Payload {
+                        element: name.to_string(),
+                        row,
+                        offset,
+                        reason: format!("invalid list length {len}"),
+                    })?;
+                    offset = offset
+                        .saturating_add(count.bytes_len())
+                        .saturating_add(len.saturating_mul(prop.bytes_len()));
+                }
+            }
+        }
+        if offset > data.len() {
+            return Err(eof(row));
+        }
+    }
+    Ok(offset)
+}
+
+fn decode(ty: PLYValueTypeName, bytes: &[u8], big_endian: bool) -> Option<PLYValue> {
+    let mut bytes = bytes.iter().copied();
+    if big_endian {
+        ty.from_be_bytes(&mut bytes)
+    } else {
+        ty.from_le_bytes(&mut bytes)
+    }
+}
+
+struct ColumnLayout<'a> {
+    name: &'a str,
+    ty: PLYValueTypeName,
+    /// byte offset in row
+    offset: usize,
+}
+
+/// View of element without list properties, returned by [PLYView::element]
+pub struct ElementView<'a> {
+    bytes: &'a [u8],
+    count: usize,
+    stride: usize,
+    columns: Vec<ColumnLayout<'a>>,
+    big_endian: bool,
+}
+
+impl<'a> ElementView<'a> {
+    /// Returns number of rows
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns byte size of a row
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns raw payload bytes of element
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns row at `index`
+    pub fn row(&self, index: usize) -> Option<RowView<'_>> {
+        if index >= self.count {
+            return None;
+        }
+        let start = index * self.stride;
+        Some(RowView {
+            bytes: &self.bytes[start..start + self.stride],
+            columns: &self.columns,
+            big_endian: self.big_endian,
+        })
+    }
+
+    /// Iterator over rows
+    pub fn rows(&self) -> impl Iterator<Item = RowView<'_>> {
+        (0..self.count).filter_map(move |i| self.row(i))
+    }
+
+    /// Returns column of property `name`
+    pub fn column(&self, name: &str) -> Option<ColumnView<'a>> {
+        let column = self.columns.iter().find(|c| c.name == name)?;
+        Some(ColumnView {
+            bytes: self.bytes,
+            count: self.count,
+            stride: self.stride,
+            offset: column.offset,
+            ty: column.ty,
+            big_endian: self.big_endian,
+        })
+    }
+
+    /// Returns column of property `name` decoded as `T`
+    ///
+    /// Returns None if there is no such property, or its type is not `T`.
+    pub fn typed_column<T: PLYScalar>(&self, name: &str) -> Option<TypedColumn<'a, T>> {
+        let column = self.column(name)?;
+        if column.ty != T::TYPE {
+            return None;
+        }
+        Some(TypedColumn {
+            column,
+            _type: PhantomData,
+        })
+    }
+}
+
+/// A row of [ElementView]
+pub struct RowView<'a> {
+    bytes: &'a [u8],
+    columns: &'a [ColumnLayout<'a>],
+    big_endian: bool,
+}
+
+impl<'a> RowView<'a> {
+    /// Returns value of `index`-th property
+    pub fn get(&self, index: usize) -> Option<PLYValue> {
+        let column = self.columns.get(index)?;
+        decode(column.ty, &self.bytes[column.offset..], self.big_endian)
+    }
+
+    /// Returns value of property `name`
+    pub fn get_by_name(&self, name: &str) -> Option<PLYValue> {
+        let index = self.columns.iter().position(|c| c.name == name)?;
+        self.get(index)
+    }
+
+    /// Returns value of property `name` as `T`, None if its type is not `T`
+    pub fn get_as<T: PLYScalar>(&self, name: &str) -> Option<T> {
+        let column = self.columns.iter().find(|c| c.name == name)?;
+        if column.ty != T::TYPE {
+            return None;
+        }
+        let bytes = &self.bytes[column.offset..column.offset + column.ty.bytes_len()];
+        Some(T::from_bytes(bytes, self.big_endian))
+    }
+
+    /// Decode all values of row
+    pub fn to_payload(&self) -> Payload {
+        Payload::new(
+            (0..self.columns.len())
+                .filter_map(|i| self.get(i))
+                .collect(),
+        )
+    }
+
+    /// Returns raw bytes of row
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+#[derive(Clone, Copy)]
+/// A column of [ElementView]
+pub struct ColumnView<'a> {
+    bytes: &'a [u8],
+    count: usize,
+    stride: usize,
+    offset: usize,
+    ty: PLYValueTypeName,
+    big_endian: bool,
+}
+
+impl<'a> ColumnView<'a> {
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn value_type(&self) -> PLYValueTypeName {
+        self.ty
+    }
+
+    /// Returns value at row `index`
+    pub fn get(&self, index: usize) -> Option<PLYValue> {
+        if index >= self.count {
+            return None;
+        }
+        decode(
+            self.ty,
+            &self.bytes[index * self.stride + self.offset..],
+            self.big_endian,
+        )
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = PLYValue> + 'a {
+        let column = *self;
+        (0..self.count).filter_map(move |i| column.get(i))
+    }
+
+    fn value_bytes(&self, index: usize) -> &'a [u8] {
+        let start = index * self.stride + self.offset;
+        &self.bytes[start..start + self.ty.bytes_len()]
+    }
+}
+
+/// A column of [ElementView] of known type `T`
+pub struct TypedColumn<'a, T> {
+    column: ColumnView<'a>,
+    _type: PhantomData<T>,
+}
+
+impl<'a, T: PLYScalar> TypedColumn<'a, T> {
+    pub fn len(&self) -> usize {
+        self.column.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.column.count == 0
+    }
+
+    /// Returns value at row `index`
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.column.count {
+            return None;
+        }
+        Some(T::from_bytes(
+            self.column.value_bytes(index),
+            self.column.big_endian,
+        ))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = T> + 'a {
+        let column = self.column;
+        (0..column.count).map(move |i| T::from_bytes(column.value_bytes(i), column.big_endian))
+    }
+}
+
+/// Rust type of a PLY scalar type, used by typed accessors of [ElementView]
+pub trait PLYScalar: Copy {
+    const TYPE: PLYValueTypeName;
+
+    /// Decode from bytes of length [PLYValueTypeName::bytes_len]
+    fn from_bytes(bytes: &[u8], big_endian: bool) -> Self;
+}
+
+macro_rules! impl_ply_scalar {
+    ($($t:ty => $name:ident),*) => {
+        $(
+            impl PLYScalar for $t {
+                const TYPE: PLYValueTypeName = PLYValueTypeName::$name;
+
+                fn from_bytes(bytes: &[u8], big_endian: bool) -> Self {
+                    let bytes = bytes.try_into().expect("bytes of type length");
+                    if big_endian {
+                        <$t>::from_be_bytes(bytes)
+                    } else {
+                        <$t>::from_le_bytes(bytes)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_ply_scalar!(
+    i8 => Char,
+    u8 => Uchar,
+    i16 => Short,
+    u16 => Ushort,
+    i32 => Int,
+    u32 => Uint,
+    f32 => Float,
+    f64 => Double
+);
+
+#[cfg(test)]
+mod test {
+    use super::PLYView;
+    use crate::{error::PLYError, PLYValue, PLYValueTypeName, Payload};
+
+    fn binary_le() -> Vec<u8> {
+        let mut data = b"\
+ply
+format binary_little_endian 1.0
+element vertex 2
+property float x
+property uchar red
+element face 2
+property list uchar int vertex_indices
+element edge 1
+property short vertex1
+property short vertex2
+end_header
+"
+        .to_vec();
+        for (x, red) in [(0.5f32, 10u8), (-1.0, 255)] {
+            data.extend_from_slice(&x.to_le_bytes());
+            data.push(red);
+        }
+        for face in [&[0i32, 1, 2][..], &[3]] {
+            data.push(face.len() as u8);
+            for i in face {
+                data.extend_from_slice(&i.to_le_bytes());
+            }
+        }
+        data.extend_from_slice(&7i16.to_le_bytes());
+        data.extend_from_slice(&(-7i16).to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_view_binary() {
+        let view = PLYView::new(binary_le()).unwrap();
+        assert_eq!(view.header().file.elements.len(), 3);
+
+        let vertex = view.element("vertex").unwrap();
+        assert_eq!(vertex.len(), 2);
+        assert_eq!(vertex.stride(), 5);
+        let row = vertex.row(1).unwrap();
+        assert_eq!(row.get(0), Some(PLYValue::Float(-1.0)));
+        assert_eq!(row.get_by_name("red"), Some(PLYValue::Uchar(255)));
+        assert_eq!(row.get_as::<u8>("red"), Some(255));
+        assert_eq!(row.get_as::<f32>("red"), None);
+        assert_eq!(
+            row.to_payload(),
+            Payload::new(vec![PLYValue::Float(-1.0), PLYValue::Uchar(255)])
+        );
+        assert!(vertex.row(2).is_none());
+
+        let x = vertex.typed_column::<f32>("x").unwrap();
+        assert_eq!(x.iter().collect::<Vec<_>>(), vec![0.5, -1.0]);
+        let red = vertex.column("red").unwrap();
+        assert_eq!(red.value_type(), PLYValueTypeName::Uchar);
+        assert_eq!(red.get(0), Some(PLYValue::Uchar(10)));
+
+        // element with list property has no view, but following element is located
+        assert!(view.element("face").is_none());
+        let edge = view.element("edge").unwrap();
+        assert_eq!(
+            edge.rows().map(|r| r.to_payload()).collect::<Vec<_>>(),
+            vec![Payload::new(vec![PLYValue::Short(7), PLYValue::Short(-7)])]
+        );
+    }
+
+    #[test]
+    fn test_view_errors() {
+        let mut data = binary_le();
+        data.truncate(data.len() - 5);
+        let len = data.len();
+        assert_eq!(
+            PLYView::new(data).err(),
+            Some(PLYError::UnexpectedEof {
+                element: "face".to_string(),
+                row: 1,
+                offset: len,
+            })
+        );
+
+        let ascii = b"ply\nformat ascii 1.0\nelement vertex 0\nproperty float x\nend_header\n";
+        assert!(matches!(
+            PLYView::new(&ascii[..]).err(),
+            Some(PLYError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_view_open() {
+        let path = std::env::temp_dir().join(format!("ply_view_{}.ply", std::process::id()));
+        std::fs::write(&path, binary_le()).unwrap();
+        let view = unsafe { PLYView::open(&path) }.unwrap();
+        let column = view
+            .element("vertex")
+            .unwrap()
+            .typed_column::<u8>("red")
+            .unwrap();
+        assert_eq!(column.get(0), Some(10));
+        drop(view);
+        std::fs::remove_file(path).unwrap();
+    }
+}
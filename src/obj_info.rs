@@ -0,0 +1,48 @@
+use std::{fmt::Display, io::Write};
+
+use crate::writer::header::PlyWriteHeader;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// Struct represent ObjInfo, line like `obj_info num_cols 640`
+///
+/// Stored words (splitted by whitespace).
+pub struct ObjInfo(pub(crate) Vec<String>);
+
+impl ObjInfo {
+    pub fn new(v: Vec<String>) -> Self {
+        Self(v)
+    }
+
+    pub fn from_string<S: Into<String>>(obj_info: S) -> ObjInfo {
+        ObjInfo(
+            obj_info
+                .into()
+                .split_whitespace()
+                .map(|v| v.to_string())
+                .collect(),
+        )
+    }
+}
+
+impl Display for ObjInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "obj_info {}", self.0.join(" "))
+    }
+}
+
+impl<T: Write> PlyWriteHeader<T> for ObjInfo {
+    fn write_header(&self, writer: &mut T) -> std::io::Result<()> {
+        writeln!(writer, "obj_info {}", self.0.join(" "))
+    }
+}
+
+#[test]
+fn test_write_obj_info() {
+    let mut writer = std::io::BufWriter::new(Vec::new());
+    let obj_info = ObjInfo(vec!["num_cols".to_string(), "640".to_string()]);
+    obj_info.write_header(&mut writer).unwrap();
+    assert_eq!(
+        writer.into_inner().unwrap(),
+        "obj_info num_cols 640\n".as_bytes(),
+    )
+}
@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use crate::PLYValue;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Payload(pub(crate) Vec<PLYValue>);
 
 impl Payload {
@@ -45,3 +45,71 @@ impl DerefMut for Payload {
         &mut self.0
     }
 }
+
+#[derive(Debug, Clone, PartialEq)]
+/// Value of a property in [MixedPayload]
+pub enum MixedValue {
+    Scalar(PLYValue),
+    List(Vec<PLYValue>),
+}
+
+impl MixedValue {
+    /// Returns scalar value, or None if `self` is list
+    pub fn as_scalar(&self) -> Option<PLYValue> {
+        match self {
+            MixedValue::Scalar(v) => Some(*v),
+            MixedValue::List(_) => None,
+        }
+    }
+
+    /// Returns list values, or None if `self` is scalar
+    pub fn as_list(&self) -> Option<&[PLYValue]> {
+        match self {
+            MixedValue::Scalar(_) => None,
+            MixedValue::List(v) => Some(v),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// Payload of [MixedProperty](crate::MixedProperty) element, one value per property
+pub struct MixedPayload(pub(crate) Vec<MixedValue>);
+
+impl MixedPayload {
+    pub fn new(v: Vec<MixedValue>) -> Self {
+        Self(v)
+    }
+
+    pub fn push_value(&mut self, v: MixedValue) {
+        self.0.push(v);
+    }
+}
+
+impl IntoIterator for MixedPayload {
+    type Item = MixedValue;
+
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl From<Vec<MixedValue>> for MixedPayload {
+    fn from(v: Vec<MixedValue>) -> Self {
+        Self(v)
+    }
+}
+
+impl Deref for MixedPayload {
+    type Target = [MixedValue];
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for MixedPayload {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
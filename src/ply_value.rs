@@ -14,6 +14,18 @@ pub enum PLYValueTypeName {
     Float,
     Double,
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+/// Naming convention of type names in header
+///
+/// Naming is kept for each type of property as read, so a list keeps namings of its count and value types.
+pub enum TypeNaming {
+    /// `char`, `uchar`, `short`, `ushort`, `int`, `uint`, `float`, `double`
+    #[default]
+    Classic,
+    /// `int8`, `uint8`, `int16`, `uint16`, `int32`, `uint32`, `float32`, `float64`
+    Sized,
+}
+
 impl PLYValueTypeName {
     /// Returns bytes length of type
     pub fn bytes_len(&self) -> usize {
@@ -41,6 +53,48 @@ impl PLYValueTypeName {
             PLYValueTypeName::Double => "double",
         }
     }
+    /// Returns type name in sized naming (`int8`, `float32`, ...)
+    pub fn to_sized_str(&self) -> &'static str {
+        match self {
+            PLYValueTypeName::Char => "int8",
+            PLYValueTypeName::Uchar => "uint8",
+            PLYValueTypeName::Short => "int16",
+            PLYValueTypeName::Ushort => "uint16",
+            PLYValueTypeName::Int => "int32",
+            PLYValueTypeName::Uint => "uint32",
+            PLYValueTypeName::Float => "float32",
+            PLYValueTypeName::Double => "float64",
+        }
+    }
+    /// Returns type name in `naming`
+    pub fn to_str_with(&self, naming: TypeNaming) -> &'static str {
+        match naming {
+            TypeNaming::Classic => self.to_str(),
+            TypeNaming::Sized => self.to_sized_str(),
+        }
+    }
+    /// Parse type name of either naming, returns type and naming of `s`
+    pub fn from_str_with_naming(s: &str) -> PLYResult<(PLYValueTypeName, TypeNaming)> {
+        match s {
+            "char" => Ok((Self::Char, TypeNaming::Classic)),
+            "uchar" => Ok((Self::Uchar, TypeNaming::Classic)),
+            "short" => Ok((Self::Short, TypeNaming::Classic)),
+            "ushort" => Ok((Self::Ushort, TypeNaming::Classic)),
+            "int" => Ok((Self::Int, TypeNaming::Classic)),
+            "uint" => Ok((Self::Uint, TypeNaming::Classic)),
+            "float" => Ok((Self::Float, TypeNaming::Classic)),
+            "double" => Ok((Self::Double, TypeNaming::Classic)),
+            "int8" => Ok((Self::Char, TypeNaming::Sized)),
+            "uint8" => Ok((Self::Uchar, TypeNaming::Sized)),
+            "int16" => Ok((Self::Short, TypeNaming::Sized)),
+            "uint16" => Ok((Self::Ushort, TypeNaming::Sized)),
+            "int32" => Ok((Self::Int, TypeNaming::Sized)),
+            "uint32" => Ok((Self::Uint, TypeNaming::Sized)),
+            "float32" => Ok((Self::Float, TypeNaming::Sized)),
+            "float64" => Ok((Self::Double, TypeNaming::Sized)),
+            _ => Err(PLYError::UnknownPLYTypeIdentifier),
+        }
+    }
     /// parse `&str` into [PLYValue]
     pub fn parse(&self, value: &str) -> PLYResult<PLYValue> {
         match self {
@@ -71,74 +125,34 @@ impl PLYValueTypeName {
         }
     }
     /// from big-endian
-    pub fn from_be_bytes<I: Iterator<Item = u8>>(&self, bytes: &mut I) -> PLYValue {
-        match self {
-            PLYValueTypeName::Char => PLYValue::Char(i8::from_be_bytes([bytes.next().unwrap()])),
-            PLYValueTypeName::Uchar => PLYValue::Uchar(u8::from_be_bytes([bytes.next().unwrap()])),
-            PLYValueTypeName::Short => {
-                PLYValue::Short(i16::from_be_bytes(std::array::from_fn(|_| {
-                    bytes.next().unwrap()
-                })))
-            }
-            PLYValueTypeName::Ushort => {
-                PLYValue::Ushort(u16::from_be_bytes(std::array::from_fn(|_| {
-                    bytes.next().unwrap()
-                })))
-            }
-            PLYValueTypeName::Int => PLYValue::Int(i32::from_be_bytes(std::array::from_fn(|_| {
-                bytes.next().unwrap()
-            }))),
-            PLYValueTypeName::Uint => {
-                PLYValue::Uint(u32::from_be_bytes(std::array::from_fn(|_| {
-                    bytes.next().unwrap()
-                })))
-            }
-            PLYValueTypeName::Float => {
-                PLYValue::Float(f32::from_be_bytes(std::array::from_fn(|_| {
-                    bytes.next().unwrap()
-                })))
-            }
-            PLYValueTypeName::Double => {
-                PLYValue::Double(f64::from_be_bytes(std::array::from_fn(|_| {
-                    bytes.next().unwrap()
-                })))
-            }
-        }
+    ///
+    /// Returns None if `bytes` ends before value is read.
+    pub fn from_be_bytes<I: Iterator<Item = u8>>(&self, bytes: &mut I) -> Option<PLYValue> {
+        Some(match self {
+            PLYValueTypeName::Char => PLYValue::Char(i8::from_be_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Uchar => PLYValue::Uchar(u8::from_be_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Short => PLYValue::Short(i16::from_be_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Ushort => PLYValue::Ushort(u16::from_be_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Int => PLYValue::Int(i32::from_be_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Uint => PLYValue::Uint(u32::from_be_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Float => PLYValue::Float(f32::from_be_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Double => PLYValue::Double(f64::from_be_bytes(next_bytes(bytes)?)),
+        })
     }
     /// from little-endian
-    pub fn from_le_bytes<I: Iterator<Item = u8>>(&self, bytes: &mut I) -> PLYValue {
-        match self {
-            PLYValueTypeName::Char => PLYValue::Char(i8::from_le_bytes([bytes.next().unwrap()])),
-            PLYValueTypeName::Uchar => PLYValue::Uchar(u8::from_le_bytes([bytes.next().unwrap()])),
-            PLYValueTypeName::Short => {
-                PLYValue::Short(i16::from_le_bytes(std::array::from_fn(|_| {
-                    bytes.next().unwrap()
-                })))
-            }
-            PLYValueTypeName::Ushort => {
-                PLYValue::Ushort(u16::from_le_bytes(std::array::from_fn(|_| {
-                    bytes.next().unwrap()
-                })))
-            }
-            PLYValueTypeName::Int => PLYValue::Int(i32::from_le_bytes(std::array::from_fn(|_| {
-                bytes.next().unwrap()
-            }))),
-            PLYValueTypeName::Uint => {
-                PLYValue::Uint(u32::from_le_bytes(std::array::from_fn(|_| {
-                    bytes.next().unwrap()
-                })))
-            }
-            PLYValueTypeName::Float => {
-                PLYValue::Float(f32::from_le_bytes(std::array::from_fn(|_| {
-                    bytes.next().unwrap()
-                })))
-            }
-            PLYValueTypeName::Double => {
-                PLYValue::Double(f64::from_le_bytes(std::array::from_fn(|_| {
-                    bytes.next().unwrap()
-                })))
-            }
-        }
+    ///
+    /// Returns None if `bytes` ends before value is read.
+    pub fn from_le_bytes<I: Iterator<Item = u8>>(&self, bytes: &mut I) -> Option<PLYValue> {
+        Some(match self {
+            PLYValueTypeName::Char => PLYValue::Char(i8::from_le_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Uchar => PLYValue::Uchar(u8::from_le_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Short => PLYValue::Short(i16::from_le_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Ushort => PLYValue::Ushort(u16::from_le_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Int => PLYValue::Int(i32::from_le_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Uint => PLYValue::Uint(u32::from_le_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Float => PLYValue::Float(f32::from_le_bytes(next_bytes(bytes)?)),
+            PLYValueTypeName::Double => PLYValue::Double(f64::from_le_bytes(next_bytes(bytes)?)),
+        })
     }
     pub fn zero(&self) -> PLYValue {
         match self {
@@ -153,6 +167,14 @@ impl PLYValueTypeName {
         }
     }
 }
+/// Take `N` bytes from iterator
+fn next_bytes<I: Iterator<Item = u8>, const N: usize>(bytes: &mut I) -> Option<[u8; N]> {
+    let mut buf = [0u8; N];
+    for b in buf.iter_mut() {
+        *b = bytes.next()?;
+    }
+    Some(buf)
+}
 pub(crate) trait PlyTryFrom<T> {
     fn try_from(&self, v: T) -> PLYResult<PLYValue>;
 }
@@ -186,26 +208,37 @@ impl PlyTryFrom<usize> for PLYValueTypeName {
 fn test_from_be_bytes() {
     assert_eq!(
         PLYValueTypeName::Char.from_be_bytes(&mut vec![8u8].into_iter()),
-        PLYValue::Char(8)
+        Some(PLYValue::Char(8))
+    );
+    assert_eq!(
+        PLYValueTypeName::Int.from_be_bytes(&mut vec![0u8, 0, 1].into_iter()),
+        None
     );
 }
 impl FromStr for PLYValueTypeName {
     type Err = PLYError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "char" => Ok(Self::Char),
-            "uchar" => Ok(Self::Uchar),
-            "short" => Ok(Self::Short),
-            "ushort" => Ok(Self::Ushort),
-            "int" => Ok(Self::Int),
-            "uint" => Ok(Self::Uint),
-            "float" => Ok(Self::Float),
-            "double" => Ok(Self::Double),
-            _ => Err(PLYError::UnknownPLYTypeIdentifier),
-        }
+        Self::from_str_with_naming(s).map(|(t, _)| t)
     }
 }
+#[test]
+fn test_from_str_sized() {
+    assert_eq!("float32".parse(), Ok(PLYValueTypeName::Float));
+    assert_eq!("uint8".parse(), Ok(PLYValueTypeName::Uchar));
+    assert_eq!(
+        PLYValueTypeName::from_str_with_naming("int16"),
+        Ok((PLYValueTypeName::Short, TypeNaming::Sized))
+    );
+    assert_eq!(
+        PLYValueTypeName::from_str_with_naming("short"),
+        Ok((PLYValueTypeName::Short, TypeNaming::Classic))
+    );
+    assert_eq!(
+        "float33".parse::<PLYValueTypeName>(),
+        Err(PLYError::UnknownPLYTypeIdentifier)
+    );
+}
 #[derive(Clone, Copy, Debug, PartialEq)]
 /// Scalar data types a property may have (with value)
 pub enum PLYValue {
@@ -304,9 +337,9 @@ impl TryInto<usize> for PLYValue {
     fn try_into(self) -> Result<usize, Self::Error> {
         match self {
             PLYValue::Char(v) => v.try_into().map_err(|_e| PLYError::TryIntoUsizeEr),
-            PLYValue::Uchar(v) => v.try_into().map_err(|_e| PLYError::TryIntoUsizeEr),
+            PLYValue::Uchar(v) => Ok(v.into()),
             PLYValue::Short(v) => v.try_into().map_err(|_e| PLYError::TryIntoUsizeEr),
-            PLYValue::Ushort(v) => v.try_into().map_err(|_e| PLYError::TryIntoUsizeEr),
+            PLYValue::Ushort(v) => Ok(v.into()),
             PLYValue::Int(v) => v.try_into().map_err(|_e| PLYError::TryIntoUsizeEr),
             PLYValue::Uint(v) => v.try_into().map_err(|_e| PLYError::TryIntoUsizeEr),
             PLYValue::Float(_v) => Err(PLYError::TryIntoUsizeEr),
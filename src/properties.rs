@@ -3,3 +3,6 @@ pub use list_property::*;
 
 mod property;
 pub use property::*;
+
+mod mixed_property;
+pub use mixed_property::*;
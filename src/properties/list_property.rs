@@ -2,9 +2,16 @@ use std::io::Write;
 
 use crate::{
     payload::Payload,
-    ply_value::PlyTryFrom,
-    writer::{header::PlyWriteHeader, payload::WritePayload},
-    PLYValue, PLYValueTypeName,
+    properties::mixed_property::list_len,
+    writer::{
+        ascii::{AsciiOptions, AsciiValue, FloatFormat},
+        header::PlyWriteHeader,
+        payload::{
+            encode_value_be, encode_value_le, encode_values_be, encode_values_le, WritePayload,
+        },
+        WriteOptions,
+    },
+    PLYValueTypeName, TypeNaming,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -13,6 +20,10 @@ pub struct PropertyList {
     pub(crate) count: PLYValueTypeName,
     pub(crate) prop: PLYValueTypeName,
     pub(crate) name: String,
+    /// naming of `count` type the property was declared with
+    pub(crate) count_naming: TypeNaming,
+    /// naming of `prop` type the property was declared with
+    pub(crate) naming: TypeNaming,
 }
 
 impl PropertyList {
@@ -25,6 +36,8 @@ impl PropertyList {
             count,
             prop,
             name: name.into(),
+            count_naming: TypeNaming::Classic,
+            naming: TypeNaming::Classic,
         }
     }
 }
@@ -72,11 +85,17 @@ mod test {
 
 impl<T: Write> PlyWriteHeader<T> for PropertyList {
     fn write_header(&self, writer: &mut T) -> std::io::Result<()> {
+        self.write_header_with(writer, &WriteOptions::default())
+    }
+
+    fn write_header_with(&self, writer: &mut T, options: &WriteOptions) -> std::io::Result<()> {
         writeln!(
             writer,
             "property list {} {} {}",
-            self.count.to_str(),
-            self.prop.to_str(),
+            self.count
+                .to_str_with(options.type_naming.unwrap_or(self.count_naming)),
+            self.prop
+                .to_str_with(options.type_naming.unwrap_or(self.naming)),
             self.name
         )
     }
@@ -90,6 +109,8 @@ fn test_write_property_list() {
         name: "vertex".to_string(),
         count: PLYValueTypeName::Uchar,
         prop: PLYValueTypeName::Float,
+        count_naming: TypeNaming::Classic,
+        naming: TypeNaming::Classic,
     };
     property.write_header(&mut writer).unwrap();
     assert_eq!(
@@ -101,64 +122,35 @@ fn test_write_property_list() {
 impl<T: Write> WritePayload<T> for PropertyList {
     type Payload = Payload;
 
-    fn write_payload_ascii(&self, payload: &Payload, writer: &mut T) -> std::io::Result<()> {
+    fn write_payload_ascii(
+        &self,
+        payload: &Payload,
+        formats: &[FloatFormat],
+        writer: &mut T,
+    ) -> std::io::Result<()> {
         let line = payload
             .iter()
-            .map(|v| format!("{v}"))
+            .map(|v| AsciiValue(v, formats[0]).to_string())
             .collect::<Vec<_>>()
             .join(" ");
         writeln!(writer, "{} {}", payload.len(), line)
     }
 
-    fn write_payload_be(&self, payload: &Payload, writer: &mut T) -> std::io::Result<()> {
-        match self.count.try_from(payload.len()).unwrap() {
-            PLYValue::Char(v) => writer.write(&v.to_be_bytes())?,
-            PLYValue::Uchar(v) => writer.write(&v.to_be_bytes())?,
-            PLYValue::Short(v) => writer.write(&v.to_be_bytes())?,
-            PLYValue::Ushort(v) => writer.write(&v.to_be_bytes())?,
-            PLYValue::Int(v) => writer.write(&v.to_be_bytes())?,
-            PLYValue::Uint(v) => writer.write(&v.to_be_bytes())?,
-            PLYValue::Float(v) => writer.write(&v.to_be_bytes())?,
-            PLYValue::Double(v) => writer.write(&v.to_be_bytes())?,
-        };
-        for v in payload.iter() {
-            match v {
-                PLYValue::Char(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Uchar(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Short(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Ushort(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Int(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Uint(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Float(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Double(v) => writer.write(&v.to_be_bytes())?,
-            };
-        }
+    fn ascii_formats(&self, options: &AsciiOptions) -> Vec<FloatFormat> {
+        vec![options.format_of(&self.name, self.prop)]
+    }
+
+    fn encode_be(&self, payload: &Payload, buf: &mut Vec<u8>) -> std::io::Result<()> {
+        let len = list_len(self.count, payload.len())?;
+        encode_value_be(&len, buf);
+        encode_values_be(&payload.0, buf);
         Ok(())
     }
 
-    fn write_payload_le(&self, payload: &Self::Payload, writer: &mut T) -> std::io::Result<()> {
-        match self.count.try_from(payload.len()).unwrap() {
-            PLYValue::Char(v) => writer.write(&v.to_le_bytes())?,
-            PLYValue::Uchar(v) => writer.write(&v.to_le_bytes())?,
-            PLYValue::Short(v) => writer.write(&v.to_le_bytes())?,
-            PLYValue::Ushort(v) => writer.write(&v.to_le_bytes())?,
-            PLYValue::Int(v) => writer.write(&v.to_le_bytes())?,
-            PLYValue::Uint(v) => writer.write(&v.to_le_bytes())?,
-            PLYValue::Float(v) => writer.write(&v.to_le_bytes())?,
-            PLYValue::Double(v) => writer.write(&v.to_le_bytes())?,
-        };
-        for v in payload.iter() {
-            match v {
-                PLYValue::Char(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Uchar(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Short(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Ushort(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Int(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Uint(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Float(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Double(v) => writer.write(&v.to_le_bytes())?,
-            };
-        }
+    fn encode_le(&self, payload: &Payload, buf: &mut Vec<u8>) -> std::io::Result<()> {
+        let len = list_len(self.count, payload.len())?;
+        encode_value_le(&len, buf);
+        encode_values_le(&payload.0, buf);
         Ok(())
     }
 }
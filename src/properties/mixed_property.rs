@@ -0,0 +1,504 @@
+use std::io::{self, Write};
+
+use crate::{
+    error::{PLYError, PLYResult},
+    payload::{MixedPayload, MixedValue},
+    ply_value::PlyTryFrom,
+    writer::{
+        ascii::{AsciiOptions, AsciiValue, FloatFormat},
+        header::PlyWriteHeader,
+        payload::{
+            encode_value_be, encode_value_le, encode_values_be, encode_values_le, WritePayload,
+        },
+        WriteOptions,
+    },
+    PLYValueTypeName, Property, PropertyList, TypeNaming,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Type of a property, scalar or list
+pub enum PropertyKind {
+    /// property "prop" "name"
+    Scalar(PLYValueTypeName),
+    /// property list "length-type" "prop-type" "name"
+    List {
+        count: PLYValueTypeName,
+        prop: PLYValueTypeName,
+    },
+}
+
+impl PropertyKind {
+    pub fn is_list(&self) -> bool {
+        matches!(self, PropertyKind::List { .. })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// Ordered scalar and list properties of an element
+///
+/// Used for element which cannot be represented by [Property] or [PropertyList],
+/// like face with `property list uchar int vertex_indices` and `property uchar red`,
+/// or face with both `vertex_indices` and `texcoord` lists.
+pub struct MixedProperty {
+    pub(crate) kinds: Vec<PropertyKind>,
+    pub(crate) names: Vec<String>,
+    /// naming of type names each property was declared with, of value type for list
+    pub(crate) namings: Vec<TypeNaming>,
+    /// naming of count type each list was declared with, [TypeNaming::Classic] for scalar
+    pub(crate) count_namings: Vec<TypeNaming>,
+}
+
+impl MixedProperty {
+    pub fn new() -> MixedProperty {
+        Self::default()
+    }
+    /// Push `property "prop" "name"`
+    pub fn push_prop<S: Into<String>>(&mut self, name: S, property: PLYValueTypeName) {
+        self.kinds.push(PropertyKind::Scalar(property));
+        self.names.push(name.into());
+        self.namings.push(TypeNaming::Classic);
+        self.count_namings.push(TypeNaming::Classic);
+    }
+    /// Push `property list "count" "prop" "name"`
+    pub fn push_list_prop<S: Into<String>>(
+        &mut self,
+        name: S,
+        count: PLYValueTypeName,
+        prop: PLYValueTypeName,
+    ) {
+        self.kinds.push(PropertyKind::List { count, prop });
+        self.names.push(name.into());
+        self.namings.push(TypeNaming::Classic);
+        self.count_namings.push(TypeNaming::Classic);
+    }
+    /// Push property declared with `naming`, and `count_naming` for list
+    pub(crate) fn push_kind(
+        &mut self,
+        name: String,
+        kind: PropertyKind,
+        naming: TypeNaming,
+        count_naming: TypeNaming,
+    ) {
+        self.kinds.push(kind);
+        self.names.push(name);
+        self.namings.push(naming);
+        self.count_namings.push(count_naming);
+    }
+    pub fn is_empty(&self) -> bool {
+        debug_assert_eq!(self.kinds.is_empty(), self.names.is_empty());
+        self.kinds.is_empty()
+    }
+    pub fn len(&self) -> usize {
+        debug_assert_eq!(self.kinds.len(), self.names.len());
+        self.kinds.len()
+    }
+    /// Iterator over element property (name, kind)
+    pub fn iter(&self) -> impl Iterator<Item = (&str, PropertyKind)> {
+        self.names
+            .iter()
+            .map(|x| x.as_str())
+            .zip(self.kinds.iter().copied())
+    }
+    /// Check that `payload` has a value of declared kind and type for each property
+    pub(crate) fn check_payload(&self, payload: &MixedPayload) -> PLYResult<()> {
+        if self.len() != payload.len() {
+            return Err(PLYError::PropertyLengthErr);
+        }
+        if !payload
+            .iter()
+            .zip(self.kinds.iter())
+            .all(|(v, kind)| match (v, kind) {
+                (MixedValue::Scalar(v), PropertyKind::Scalar(t)) => v.value_type() == *t,
+                (MixedValue::List(list), PropertyKind::List { prop, .. }) => {
+                    list.iter().all(|v| v.value_type() == *prop)
+                }
+                _ => false,
+            })
+        {
+            return Err(PLYError::MissmatchDataType);
+        }
+        Ok(())
+    }
+}
+
+impl From<Property> for MixedProperty {
+    fn from(p: Property) -> Self {
+        Self {
+            kinds: p.props.into_iter().map(PropertyKind::Scalar).collect(),
+            count_namings: vec![TypeNaming::Classic; p.namings.len()],
+            names: p.names,
+            namings: p.namings,
+        }
+    }
+}
+
+impl From<PropertyList> for MixedProperty {
+    fn from(p: PropertyList) -> Self {
+        Self {
+            kinds: vec![PropertyKind::List {
+                count: p.count,
+                prop: p.prop,
+            }],
+            names: vec![p.name],
+            namings: vec![p.naming],
+            count_namings: vec![p.count_naming],
+        }
+    }
+}
+
+impl<S: Into<String>> From<Vec<(S, PropertyKind)>> for MixedProperty {
+    fn from(v: Vec<(S, PropertyKind)>) -> Self {
+        let (names, kinds): (_, Vec<_>) = v.into_iter().map(|(s, p)| (s.into(), p)).unzip();
+        Self {
+            names,
+            namings: vec![TypeNaming::Classic; kinds.len()],
+            count_namings: vec![TypeNaming::Classic; kinds.len()],
+            kinds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        error::PLYError, GenericElement, MixedPayload, MixedProperty, MixedValue, PLYValue,
+        PLYValueTypeName, Payload, PropertyList,
+    };
+
+    #[test]
+    fn test_push_mixed_payload() {
+        let mut element = {
+            let mut props = MixedProperty::new();
+            props.push_list_prop(
+                "vertex_indices",
+                PLYValueTypeName::Uchar,
+                PLYValueTypeName::Int,
+            );
+            props.push_prop("red", PLYValueTypeName::Uchar);
+            GenericElement::new(props)
+        };
+
+        let result = element.push_payload(MixedPayload::new(vec![
+            MixedValue::List(vec![PLYValue::Int(0), PLYValue::Int(1), PLYValue::Int(2)]),
+            MixedValue::Scalar(PLYValue::Uchar(255)),
+        ]));
+        assert!(result.is_ok());
+        assert!(element.count() == 1);
+
+        let result = element.push_payload(MixedPayload::new(vec![
+            MixedValue::Scalar(PLYValue::Uchar(255)),
+            MixedValue::List(vec![PLYValue::Int(0), PLYValue::Int(1), PLYValue::Int(2)]),
+        ]));
+        assert_eq!(result, Err(PLYError::MissmatchDataType));
+
+        let result = element.push_payload(MixedPayload::new(vec![MixedValue::List(vec![
+            PLYValue::Int(0),
+            PLYValue::Int(1),
+            PLYValue::Int(2),
+        ])]));
+        assert_eq!(result, Err(PLYError::PropertyLengthErr));
+        assert!(element.count() == 1);
+    }
+
+    #[test]
+    fn test_multiple_list_values() {
+        let mut element: GenericElement<MixedProperty, MixedPayload> = GenericElement::from({
+            let mut element = GenericElement::new(PropertyList::new(
+                "vertex_indices",
+                PLYValueTypeName::Uchar,
+                PLYValueTypeName::Int,
+            ));
+            element
+                .push_payload(Payload::new(vec![
+                    PLYValue::Int(0),
+                    PLYValue::Int(1),
+                    PLYValue::Int(2),
+                ]))
+                .unwrap();
+            element
+        });
+        element
+            .props
+            .push_list_prop("texcoord", PLYValueTypeName::Uchar, PLYValueTypeName::Float);
+        element.payloads[0].push_value(MixedValue::List(vec![
+            PLYValue::Float(0.0),
+            PLYValue::Float(0.5),
+        ]));
+        element
+            .push_payload(MixedPayload::new(vec![
+                MixedValue::List(vec![PLYValue::Int(2), PLYValue::Int(3)]),
+                MixedValue::List(vec![PLYValue::Float(1.0)]),
+            ]))
+            .unwrap();
+
+        assert_eq!(
+            element.list_values("texcoord").unwrap().collect::<Vec<_>>(),
+            vec![
+                &[PLYValue::Float(0.0), PLYValue::Float(0.5)][..],
+                &[PLYValue::Float(1.0)][..]
+            ]
+        );
+        assert_eq!(
+            element
+                .list_values("vertex_indices")
+                .unwrap()
+                .map(|l| l.len())
+                .collect::<Vec<_>>(),
+            vec![3, 2]
+        );
+        assert!(element.list_values("red").is_none());
+        assert!(element.scalar_values("texcoord").is_none());
+    }
+}
+
+impl<T: Write> PlyWriteHeader<T> for MixedProperty {
+    fn write_header(&self, writer: &mut T) -> std::io::Result<()> {
+        self.write_header_with(writer, &WriteOptions::default())
+    }
+
+    fn write_header_with(&self, writer: &mut T, options: &WriteOptions) -> std::io::Result<()> {
+        for (((name, kind), naming), count_naming) in self
+            .iter()
+            .zip(self.namings.iter())
+            .zip(self.count_namings.iter())
+        {
+            let naming = options.type_naming.unwrap_or(*naming);
+            match kind {
+                PropertyKind::Scalar(prop) => {
+                    writeln!(writer, "property {} {}", prop.to_str_with(naming), name)?
+                }
+                PropertyKind::List { count, prop } => writeln!(
+                    writer,
+                    "property list {} {} {}",
+                    count.to_str_with(options.type_naming.unwrap_or(*count_naming)),
+                    prop.to_str_with(naming),
+                    name
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_write_mixed_property() {
+    use crate::*;
+    let mut writer = std::io::BufWriter::new(Vec::new());
+    let property = MixedProperty::from(vec![
+        ("x", PropertyKind::Scalar(PLYValueTypeName::Float)),
+        (
+            "vertex_indices",
+            PropertyKind::List {
+                count: PLYValueTypeName::Uchar,
+                prop: PLYValueTypeName::Int,
+            },
+        ),
+        ("red", PropertyKind::Scalar(PLYValueTypeName::Uchar)),
+    ]);
+    property.write_header(&mut writer).unwrap();
+    assert_eq!(
+        writer.into_inner().unwrap(),
+        "\
+property float x
+property list uchar int vertex_indices
+property uchar red
+"
+        .as_bytes(),
+    )
+}
+
+#[test]
+fn test_write_mixed_kind_mismatch() {
+    use crate::*;
+    let mut props = MixedProperty::new();
+    props.push_prop("x", PLYValueTypeName::Int);
+    let mut element = GenericElement::new(props);
+    element
+        .push_payload(MixedPayload::new(vec![MixedValue::Scalar(PLYValue::Int(
+            1,
+        ))]))
+        .unwrap();
+    element.property_mut().kinds[0] = PropertyKind::List {
+        count: PLYValueTypeName::Uchar,
+        prop: PLYValueTypeName::Int,
+    };
+    for format in [
+        Format::Ascii {
+            version: "1.0".to_string(),
+        },
+        Format::BinaryLittleEndian {
+            version: "1.0".to_string(),
+        },
+    ] {
+        let mut ply = PLYFile::new(format);
+        ply.elements.push(Element::MixedElement {
+            name: "face".to_string(),
+            elements: element.clone(),
+        });
+        let mut writer = std::io::BufWriter::new(Vec::new());
+        let err = ply.write(&mut writer).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
+
+#[test]
+fn test_write_mixed_row_len() {
+    use crate::writer::payload::write_element_payload;
+    use crate::*;
+    let mut props = MixedProperty::new();
+    props.push_prop("x", PLYValueTypeName::Uchar);
+    props.push_prop("y", PLYValueTypeName::Uchar);
+    let mut element = GenericElement::new(props);
+    for row in [[1, 2], [3, 4]] {
+        element
+            .push_payload(MixedPayload::new(
+                row.iter()
+                    .map(|v| MixedValue::Scalar(PLYValue::Uchar(*v)))
+                    .collect(),
+            ))
+            .unwrap();
+    }
+    let short = {
+        let mut element = element.clone();
+        element.payload_mut()[1].0.pop();
+        element
+    };
+    let long = {
+        let mut element = element.clone();
+        element.payload_mut()[1]
+            .0
+            .push(MixedValue::Scalar(PLYValue::Uchar(5)));
+        element
+    };
+    for (format, first_row) in [
+        (
+            Format::Ascii {
+                version: "1.0".to_string(),
+            },
+            &b"1 2\n"[..],
+        ),
+        (
+            Format::BinaryBigEndian {
+                version: "1.0".to_string(),
+            },
+            &[1, 2][..],
+        ),
+        (
+            Format::BinaryLittleEndian {
+                version: "1.0".to_string(),
+            },
+            &[1, 2][..],
+        ),
+    ] {
+        for element in [&short, &long] {
+            // only the rows before the failing one are written
+            let mut writer = Vec::new();
+            let err =
+                write_element_payload(element, &mut writer, &format, &AsciiOptions::default())
+                    .unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            assert_eq!(writer, first_row);
+        }
+    }
+}
+
+/// Convert list length into value of `count` type
+pub(crate) fn list_len(count: PLYValueTypeName, len: usize) -> io::Result<crate::PLYValue> {
+    count.try_from(len).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("list length {len} does not fit in {}", count.to_str()),
+        )
+    })
+}
+
+/// Error of `value` given to property of the other kind, scalar for list or list for scalar
+fn kind_mismatch(value: &MixedValue) -> io::Error {
+    let reason = match value {
+        MixedValue::Scalar(_) => "scalar value for list property",
+        MixedValue::List(_) => "list value for scalar property",
+    };
+    io::Error::new(io::ErrorKind::InvalidInput, reason)
+}
+
+/// Error of row with other number of values than `props`
+fn check_row_len(props: &MixedProperty, payload: &MixedPayload) -> io::Result<()> {
+    if payload.len() != props.kinds.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "row has {} values for {} properties",
+                payload.len(),
+                props.kinds.len()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+impl<T: Write> WritePayload<T> for MixedProperty {
+    type Payload = MixedPayload;
+
+    fn write_payload_ascii(
+        &self,
+        payload: &MixedPayload,
+        formats: &[FloatFormat],
+        writer: &mut T,
+    ) -> std::io::Result<()> {
+        check_row_len(self, payload)?;
+        let mut values = Vec::with_capacity(payload.len());
+        for ((v, kind), format) in payload.iter().zip(self.kinds.iter()).zip(formats) {
+            match (v, kind) {
+                (MixedValue::Scalar(v), PropertyKind::Scalar(_)) => {
+                    values.push(AsciiValue(v, *format).to_string())
+                }
+                (MixedValue::List(list), PropertyKind::List { .. }) => {
+                    values.push(list.len().to_string());
+                    values.extend(list.iter().map(|v| AsciiValue(v, *format).to_string()));
+                }
+                (v, _) => return Err(kind_mismatch(v)),
+            }
+        }
+        writeln!(writer, "{}", values.join(" "))
+    }
+
+    fn ascii_formats(&self, options: &AsciiOptions) -> Vec<FloatFormat> {
+        self.iter()
+            .map(|(name, kind)| match kind {
+                PropertyKind::Scalar(ty) | PropertyKind::List { prop: ty, .. } => {
+                    options.format_of(name, ty)
+                }
+            })
+            .collect()
+    }
+
+    fn encode_be(&self, payload: &MixedPayload, buf: &mut Vec<u8>) -> std::io::Result<()> {
+        check_row_len(self, payload)?;
+        for (v, kind) in payload.iter().zip(self.kinds.iter()) {
+            match (v, kind) {
+                (MixedValue::List(list), PropertyKind::List { count, .. }) => {
+                    encode_value_be(&list_len(*count, list.len())?, buf);
+                    encode_values_be(list, buf);
+                }
+                (MixedValue::Scalar(v), PropertyKind::Scalar(_)) => encode_value_be(v, buf),
+                (v, _) => return Err(kind_mismatch(v)),
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_le(&self, payload: &MixedPayload, buf: &mut Vec<u8>) -> std::io::Result<()> {
+        check_row_len(self, payload)?;
+        for (v, kind) in payload.iter().zip(self.kinds.iter()) {
+            match (v, kind) {
+                (MixedValue::List(list), PropertyKind::List { count, .. }) => {
+                    encode_value_le(&list_len(*count, list.len())?, buf);
+                    encode_values_le(list, buf);
+                }
+                (MixedValue::Scalar(v), PropertyKind::Scalar(_)) => encode_value_le(v, buf),
+                (v, _) => return Err(kind_mismatch(v)),
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,8 +2,13 @@ use std::io::Write;
 
 use crate::{
     payload::Payload,
-    writer::{header::PlyWriteHeader, payload::WritePayload},
-    PLYValue, PLYValueTypeName,
+    writer::{
+        ascii::{AsciiOptions, AsciiValue, FloatFormat},
+        header::PlyWriteHeader,
+        payload::{encode_values_be, encode_values_le, WritePayload},
+        WriteOptions,
+    },
+    PLYValueTypeName, TypeNaming,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -11,6 +16,8 @@ use crate::{
 pub struct Property {
     pub(crate) props: Vec<PLYValueTypeName>,
     pub(crate) names: Vec<String>,
+    /// naming of type name each property was declared with
+    pub(crate) namings: Vec<TypeNaming>,
 }
 
 impl Property {
@@ -20,6 +27,7 @@ impl Property {
     pub fn push_prop<S: Into<String>>(&mut self, name: S, property: PLYValueTypeName) {
         self.props.push(property);
         self.names.push(name.into());
+        self.namings.push(TypeNaming::Classic);
     }
     pub fn is_empty(&self) -> bool {
         debug_assert_eq!(self.props.is_empty(), self.names.is_empty());
@@ -47,8 +55,12 @@ impl Property {
 
 impl<S: Into<String>> From<Vec<(S, PLYValueTypeName)>> for Property {
     fn from(v: Vec<(S, PLYValueTypeName)>) -> Self {
-        let (names, props) = v.into_iter().map(|(s, p)| (s.into(), p)).unzip();
-        Self { names, props }
+        let (names, props): (_, Vec<_>) = v.into_iter().map(|(s, p)| (s.into(), p)).unzip();
+        Self {
+            names,
+            namings: vec![TypeNaming::Classic; props.len()],
+            props,
+        }
     }
 }
 
@@ -98,8 +110,13 @@ mod test {
 
 impl<T: Write> PlyWriteHeader<T> for Property {
     fn write_header(&self, writer: &mut T) -> std::io::Result<()> {
-        for (name, ply_type) in self.iter() {
-            writeln!(writer, "property {} {}", ply_type.to_str(), name)?
+        self.write_header_with(writer, &WriteOptions::default())
+    }
+
+    fn write_header_with(&self, writer: &mut T, options: &WriteOptions) -> std::io::Result<()> {
+        for ((name, ply_type), naming) in self.iter().zip(self.namings.iter()) {
+            let naming = options.type_naming.unwrap_or(*naming);
+            writeln!(writer, "property {} {}", ply_type.to_str_with(naming), name)?
         }
         Ok(())
     }
@@ -126,6 +143,7 @@ fn test_write_property() {
             "green".to_string(),
             "blue".to_string(),
         ],
+        namings: vec![TypeNaming::Classic; 6],
     };
     property.write_header(&mut writer).unwrap();
     assert_eq!(
@@ -145,45 +163,35 @@ property uchar blue
 impl<T: Write> WritePayload<T> for Property {
     type Payload = Payload;
 
-    fn write_payload_ascii(&self, payload: &Payload, writer: &mut T) -> std::io::Result<()> {
+    fn write_payload_ascii(
+        &self,
+        payload: &Payload,
+        formats: &[FloatFormat],
+        writer: &mut T,
+    ) -> std::io::Result<()> {
         let line = payload
             .0
             .iter()
-            .map(|v| format!("{v}"))
+            .zip(formats)
+            .map(|(v, format)| AsciiValue(v, *format).to_string())
             .collect::<Vec<_>>()
             .join(" ");
         writeln!(writer, "{line}")
     }
 
-    fn write_payload_be(&self, payload: &Payload, writer: &mut T) -> std::io::Result<()> {
-        for v in payload.0.iter() {
-            match v {
-                PLYValue::Char(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Uchar(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Short(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Ushort(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Int(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Uint(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Float(v) => writer.write(&v.to_be_bytes())?,
-                PLYValue::Double(v) => writer.write(&v.to_be_bytes())?,
-            };
-        }
+    fn ascii_formats(&self, options: &AsciiOptions) -> Vec<FloatFormat> {
+        self.iter()
+            .map(|(name, ty)| options.format_of(name, ty))
+            .collect()
+    }
+
+    fn encode_be(&self, payload: &Payload, buf: &mut Vec<u8>) -> std::io::Result<()> {
+        encode_values_be(&payload.0, buf);
         Ok(())
     }
 
-    fn write_payload_le(&self, payload: &Payload, writer: &mut T) -> std::io::Result<()> {
-        for v in payload.0.iter() {
-            match v {
-                PLYValue::Char(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Uchar(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Short(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Ushort(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Int(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Uint(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Float(v) => writer.write(&v.to_le_bytes())?,
-                PLYValue::Double(v) => writer.write(&v.to_le_bytes())?,
-            };
-        }
+    fn encode_le(&self, payload: &Payload, buf: &mut Vec<u8>) -> std::io::Result<()> {
+        encode_values_le(&payload.0, buf);
         Ok(())
     }
 }
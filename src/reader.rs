@@ -1,76 +1,609 @@
 use std::{
+    collections::HashMap,
     fs::File,
-    io::{self, BufRead, BufReader},
+    io::{BufRead, BufReader, Read},
     path::Path,
 };
 
-use crate::PLYFile;
+use crate::{
+    compression::Decompressed,
+    error::{PLYError, PLYResult, PLYWarning},
+    Element, Format, PLYFile, PLYHeader,
+};
 
 pub(crate) mod header;
-use header::{from_header_lines, read_header_lines};
+use header::{read_header, read_header_with};
+
+pub(crate) mod limits;
+use limits::{Budget, Limits};
+
+pub(crate) mod lines;
+use lines::{AsciiLines, BufLines, StrLines};
 
 pub(crate) mod payload;
 use payload::{
-    read_element_payload_be_bytes, read_element_payload_le_bytes, read_elemet_payload_ascii,
+    io_error_or, read_element_payload_be_bytes, read_element_payload_le_bytes,
+    read_elemet_payload_ascii, skip_rows_ascii, skip_rows_bytes, Bytes, ReadBytes, SkipBytes,
 };
 
+#[cfg(feature = "async")]
+pub(crate) mod asynchronous;
+
+#[cfg(feature = "rayon")]
+pub(crate) mod parallel;
+
+pub(crate) mod projection;
+use projection::{read_projected_ascii, read_projected_be, read_projected_le, Projection};
+
+pub(crate) mod stream;
+
+pub(crate) mod tokens;
+use tokens::Tokens;
+
+pub(crate) mod warnings;
+use warnings::Warnings;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// Options for [PLYFile::from_buf_read_with_options] and other `*_with_options` readers
+pub struct ReadOptions {
+    /// Names of elements to load, None loads all elements.
+    ///
+    /// Other elements are skipped without decoding their values,
+    /// and are left out of [PLYFile::elements].
+    pub elements: Option<Vec<String>>,
+    /// Names of properties to load, by element name.
+    ///
+    /// Values of other properties are consumed without being stored,
+    /// and the properties are left out of the element. Elements not in the map load all properties.
+    pub properties: HashMap<String, Vec<String>>,
+    /// Whether input which is not well-formed but can be read is accepted
+    pub mode: ParseMode,
+    /// Keep rows read before the input ended, instead of returning [PLYError::UnexpectedEof].
+    ///
+    /// Counts of truncated and following elements are reduced to the rows read,
+    /// and the rows missing are reported by [PLYWarning::MissingRows].
+    /// Partially read row is dropped. In strict [ParseMode] truncated input is still an error.
+    ///
+    /// [PLYError::UnexpectedEof]: crate::error::PLYError::UnexpectedEof
+    pub recover_truncated: bool,
+    /// Limits of header and payload, for input which is not trusted
+    pub limits: Limits,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// How readers treat input which is not well-formed, but can be read
+///
+/// Like unknown header keywords, extra words after a complete header line, data after the last element
+/// and format version other than `1.0`. Missing values are always an error.
+///
+/// Ascii rows are read as a stream of tokens, not line by line, so extra values in a row are taken
+/// as values of the next row. Strict mode detects them only as
+/// [PLYError::TrailingData](crate::error::PLYError::TrailingData) after the last element,
+/// or as an error of a following row whose values they misalign.
+pub enum ParseMode {
+    /// Accept and report [PLYWarning]s, see [PLYFile::from_buf_read_with_warnings]
+    #[default]
+    Lenient,
+    /// Reject with [PLYError](crate::error::PLYError)
+    Strict,
+}
+
+impl ReadOptions {
+    /// Returns projection of each element, elements loading all properties have None
+    fn projections(&self, elements: &[Element]) -> Vec<Option<Projection>> {
+        elements
+            .iter()
+            .map(|element| {
+                let names = self.properties.get(element.name())?;
+                Some(Projection::new(element.to_mixed_property(), names))
+            })
+            .collect()
+    }
+
+    fn loads_element(&self, name: &str) -> bool {
+        match &self.elements {
+            Some(names) => names.iter().any(|n| n == name),
+            None => true,
+        }
+    }
+}
+
 impl PLYFile {
-    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<PLYFile> {
-        let mut lines = BufReader::new(File::open(path).unwrap())
-            .lines()
-            .map(|e| e.unwrap());
-        Ok(PLYFile::from_lines(&mut lines))
-    }
-    pub fn from_lines<I: Iterator<Item = String>>(lines: &mut I) -> PLYFile {
-        let header_lines = read_header_lines(lines);
-        let mut ply = {
-            let mut iter = header_lines.into_iter();
-            from_header_lines(&mut iter)
-        };
+    pub fn from_file<P: AsRef<Path>>(path: P) -> PLYResult<PLYFile> {
+        PLYFile::from_file_with_options(path, &ReadOptions::default())
+    }
+
+    /// Read PLY file by path.
+    ///
+    /// Gzip or zstd compressed file is detected by magic bytes and decompressed on the fly,
+    /// if feature `gzip` or `zstd` is enabled.
+    pub fn from_file_with_options<P: AsRef<Path>>(
+        path: P,
+        options: &ReadOptions,
+    ) -> PLYResult<PLYFile> {
+        let mut reader = Decompressed::new(BufReader::new(File::open(path)?))?;
+        PLYFile::from_buf_read_with_options(&mut reader, options)
+    }
+
+    /// Read PLY file by path, returns warnings of lenient [ParseMode] with it.
+    pub fn from_file_with_warnings<P: AsRef<Path>>(
+        path: P,
+        options: &ReadOptions,
+    ) -> PLYResult<(PLYFile, Vec<PLYWarning>)> {
+        let mut reader = Decompressed::new(BufReader::new(File::open(path)?))?;
+        PLYFile::from_buf_read_with_warnings(&mut reader, options)
+    }
+
+    /// Read PLY file from any [Read], like HTTP body or archive entry.
+    ///
+    /// `reader` is buffered internally, wrap it in [BufReader] only to use [PLYFile::from_buf_read].
+    pub fn from_reader<R: Read>(reader: R) -> PLYResult<PLYFile> {
+        PLYFile::from_reader_with_options(reader, &ReadOptions::default())
+    }
+
+    pub fn from_reader_with_options<R: Read>(
+        reader: R,
+        options: &ReadOptions,
+    ) -> PLYResult<PLYFile> {
+        PLYFile::from_buf_read_with_options(&mut BufReader::new(reader), options)
+    }
+
+    /// Read PLY file from in-memory bytes.
+    pub fn from_bytes(bytes: &[u8]) -> PLYResult<PLYFile> {
+        let mut bytes = bytes;
+        PLYFile::from_buf_read(&mut bytes)
+    }
+
+    /// Read PLY file from [BufRead].
+    ///
+    /// Header is read line by line, then binary payload is decoded from raw bytes,
+    /// so payload may contain any byte (including `\n` and invalid UTF-8).
+    pub fn from_buf_read<R: BufRead>(reader: &mut R) -> PLYResult<PLYFile> {
+        PLYFile::from_buf_read_with_options(reader, &ReadOptions::default())
+    }
+
+    /// Read PLY file from [BufRead] with `options`.
+    ///
+    /// Reading stops after the last element to load, following elements are not read at all.
+    pub fn from_buf_read_with_options<R: BufRead>(
+        reader: &mut R,
+        options: &ReadOptions,
+    ) -> PLYResult<PLYFile> {
+        PLYFile::from_buf_read_with_warnings(reader, options).map(|(ply, _)| ply)
+    }
+
+    /// Read PLY file from [BufRead] with `options`, returns warnings of lenient [ParseMode] with it.
+    ///
+    /// Data after the last element is checked only if the last element is loaded.
+    pub fn from_buf_read_with_warnings<R: BufRead>(
+        reader: &mut R,
+        options: &ReadOptions,
+    ) -> PLYResult<(PLYFile, Vec<PLYWarning>)> {
+        let mut warnings = Warnings::new(options.mode);
+        let mut lines = BufLines::new(reader);
+        let mut ply = read_header_with(&mut lines, &mut warnings, &options.limits)?;
+        let mut budget = Budget::new(options.limits);
+        let projections = ply.project(options);
+        let reads_all = ply
+            .elements
+            .last()
+            .is_none_or(|e| options.loads_element(e.name()));
         match ply.format {
-            crate::Format::Ascii { .. } => {
-                for element in &mut ply.elements {
-                    match element {
-                        crate::Element::Element { elements, .. } => {
-                            read_elemet_payload_ascii(elements, lines)
-                        }
-                        crate::Element::ListElement { elements, .. } => {
-                            read_elemet_payload_ascii(elements, lines)
+            Format::Ascii { .. } => {
+                let mut tokens = Tokens::new(lines);
+                let result = ply
+                    .read_payload_ascii(&mut tokens, options, &projections, &mut budget)
+                    .map_err(|e| {
+                        if options.recover_truncated {
+                            unterminated_line_as_eof(&tokens, e)
+                        } else {
+                            e
                         }
-                    }
+                    });
+                if ply.recover(result, options, &mut warnings)? && reads_all {
+                    check_trailing_tokens(&mut tokens, &mut warnings)?;
                 }
             }
-            crate::Format::BinaryBigEndian { .. } => {
-                let s = lines.next().unwrap();
-                let mut bytes = s.as_bytes().iter().copied();
-                for element in &mut ply.elements {
-                    match element {
-                        crate::Element::Element { elements, .. } => {
-                            read_element_payload_be_bytes(elements, &mut bytes)
-                        }
-                        crate::Element::ListElement { elements, .. } => {
-                            read_element_payload_be_bytes(elements, &mut bytes)
-                        }
-                    }
+            Format::BinaryBigEndian { .. } => {
+                let offset = lines.position();
+                let mut bytes = Bytes::new(ReadBytes::new(lines.into_inner()), offset);
+                let result = ply
+                    .read_payload_be(&mut bytes, options, &projections, &mut budget)
+                    .map_err(|e| io_error_or(&mut bytes, e));
+                if ply.recover(result, options, &mut warnings)? && reads_all {
+                    check_trailing_bytes(&mut bytes, &mut warnings)?;
                 }
             }
-            crate::Format::BinaryLittleEndian { .. } => {
-                let s = lines.next().unwrap();
-                let mut bytes = s.as_bytes().iter().copied();
-                for element in &mut ply.elements {
-                    match element {
-                        crate::Element::Element { elements, .. } => {
-                            read_element_payload_le_bytes(elements, &mut bytes)
-                        }
-                        crate::Element::ListElement { elements, .. } => {
-                            read_element_payload_le_bytes(elements, &mut bytes)
-                        }
-                    }
+            Format::BinaryLittleEndian { .. } => {
+                let offset = lines.position();
+                let mut bytes = Bytes::new(ReadBytes::new(lines.into_inner()), offset);
+                let result = ply
+                    .read_payload_le(&mut bytes, options, &projections, &mut budget)
+                    .map_err(|e| io_error_or(&mut bytes, e));
+                if ply.recover(result, options, &mut warnings)? && reads_all {
+                    check_trailing_bytes(&mut bytes, &mut warnings)?;
+                }
+            }
+        }
+        ply.elements.retain(|e| options.loads_element(e.name()));
+        Ok((ply, warnings.into_vec()))
+    }
+
+    /// Read PLY file from lines.
+    ///
+    /// Note: binary payload cannot be split into lines without loss,
+    /// use [PLYFile::from_buf_read] for binary file.
+    pub fn from_lines<I: Iterator<Item = String>>(lines: &mut I) -> PLYResult<PLYFile> {
+        let options = ReadOptions::default();
+        let mut budget = Budget::new(options.limits);
+        let mut lines = StrLines::new(lines);
+        let mut ply = read_header(&mut lines)?;
+        match ply.format {
+            Format::Ascii { .. } => {
+                ply.read_payload_ascii(&mut Tokens::new(lines), &options, &[], &mut budget)?
+            }
+            Format::BinaryBigEndian { .. } => {
+                let bytes = lines.next_line()?.unwrap_or_default().as_bytes().to_vec();
+                let offset = lines.line_offset();
+                ply.read_payload_be(
+                    &mut Bytes::new(bytes.into_iter(), offset),
+                    &options,
+                    &[],
+                    &mut budget,
+                )?
+            }
+            Format::BinaryLittleEndian { .. } => {
+                let bytes = lines.next_line()?.unwrap_or_default().as_bytes().to_vec();
+                let offset = lines.line_offset();
+                ply.read_payload_le(
+                    &mut Bytes::new(bytes.into_iter(), offset),
+                    &options,
+                    &[],
+                    &mut budget,
+                )?
+            }
+        }
+        Ok(ply)
+    }
+
+    /// Truncate elements at the end of input if `result` is [PLYError::UnexpectedEof]
+    /// and truncated input is recovered, other errors are returned.
+    ///
+    /// Returns true if payload was read completely.
+    ///
+    /// [PLYError::UnexpectedEof]: crate::error::PLYError::UnexpectedEof
+    fn recover(
+        &mut self,
+        result: PLYResult<()>,
+        options: &ReadOptions,
+        warnings: &mut Warnings,
+    ) -> PLYResult<bool> {
+        let (element, row, offset) = match result {
+            Ok(()) => return Ok(true),
+            Err(PLYError::UnexpectedEof {
+                element,
+                row,
+                offset,
+            }) if options.recover_truncated => (element, row, offset),
+            Err(e) => return Err(e),
+        };
+        // elements before the truncated one were read (or skipped) completely
+        let truncated = self.elements.iter().position(|e| {
+            if options.loads_element(e.name()) {
+                e.payload_len() < e.count()
+            } else {
+                e.name() == element
+            }
+        });
+        let truncated = match truncated {
+            Some(truncated) => truncated,
+            None => return Ok(false),
+        };
+        for (i, e) in self.elements.iter_mut().enumerate().skip(truncated) {
+            let row = if i == truncated { row } else { 0 };
+            if !options.loads_element(e.name()) {
+                continue;
+            }
+            warnings.warn(PLYWarning::MissingRows {
+                element: e.name().to_string(),
+                row,
+                offset,
+                missing: e.count() - row,
+            })?;
+            e.truncate(row);
+        }
+        Ok(false)
+    }
+
+    /// Replace projected elements by elements with kept properties only
+    fn project(&mut self, options: &ReadOptions) -> Vec<Option<Projection>> {
+        let projections = options.projections(&self.elements);
+        for (element, projection) in self.elements.iter_mut().zip(projections.iter()) {
+            if let Some(projection) = projection {
+                *element = projection.element(element.name().to_string(), element.count());
+            }
+        }
+        projections
+    }
+
+    /// Returns elements up to the last element to load
+    fn elements_to_read(&mut self, options: &ReadOptions) -> &mut [Element] {
+        let len = self
+            .elements
+            .iter()
+            .rposition(|e| options.loads_element(e.name()))
+            .map_or(0, |i| i + 1);
+        &mut self.elements[..len]
+    }
+
+    fn read_payload_ascii<L: AsciiLines>(
+        &mut self,
+        tokens: &mut Tokens<L>,
+        options: &ReadOptions,
+        projections: &[Option<Projection>],
+        budget: &mut Budget,
+    ) -> PLYResult<()> {
+        let elements = self.elements_to_read(options);
+        for (i, element) in elements.iter_mut().enumerate() {
+            if !options.loads_element(element.name()) {
+                let props = element.to_mixed_property();
+                skip_rows_ascii(element.name(), &props, 0..element.count(), tokens)?;
+                continue;
+            }
+            if let Some(Some(projection)) = projections.get(i) {
+                read_projected_ascii(element, projection, tokens, budget)?;
+                continue;
+            }
+            match element {
+                #[cfg(feature = "rayon")]
+                Element::Element { name, elements } => {
+                    parallel::read_element_payload_ascii_par(name, elements, tokens, budget)?
+                }
+                #[cfg(not(feature = "rayon"))]
+                Element::Element { name, elements } => {
+                    read_elemet_payload_ascii(name, elements, tokens, budget)?
+                }
+                Element::ListElement { name, elements } => {
+                    read_elemet_payload_ascii(name, elements, tokens, budget)?
+                }
+                Element::MixedElement { name, elements } => {
+                    read_elemet_payload_ascii(name, elements, tokens, budget)?
                 }
             }
         }
-        ply
+        Ok(())
     }
+
+    fn read_payload_be<I: SkipBytes>(
+        &mut self,
+        bytes: &mut Bytes<I>,
+        options: &ReadOptions,
+        projections: &[Option<Projection>],
+        budget: &mut Budget,
+    ) -> PLYResult<()> {
+        let elements = self.elements_to_read(options);
+        for (i, element) in elements.iter_mut().enumerate() {
+            if !options.loads_element(element.name()) {
+                let props = element.to_mixed_property();
+                skip_rows_bytes(element.name(), &props, 0..element.count(), bytes, true)?;
+                continue;
+            }
+            if let Some(Some(projection)) = projections.get(i) {
+                read_projected_be(element, projection, bytes, budget)?;
+                continue;
+            }
+            match element {
+                #[cfg(feature = "rayon")]
+                Element::Element { name, elements } => {
+                    parallel::read_element_payload_bytes_par(name, elements, bytes, true, budget)?
+                }
+                #[cfg(not(feature = "rayon"))]
+                Element::Element { name, elements } => {
+                    read_element_payload_be_bytes(name, elements, bytes, budget)?
+                }
+                Element::ListElement { name, elements } => {
+                    read_element_payload_be_bytes(name, elements, bytes, budget)?
+                }
+                Element::MixedElement { name, elements } => {
+                    read_element_payload_be_bytes(name, elements, bytes, budget)?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_payload_le<I: SkipBytes>(
+        &mut self,
+        bytes: &mut Bytes<I>,
+        options: &ReadOptions,
+        projections: &[Option<Projection>],
+        budget: &mut Budget,
+    ) -> PLYResult<()> {
+        let elements = self.elements_to_read(options);
+        for (i, element) in elements.iter_mut().enumerate() {
+            if !options.loads_element(element.name()) {
+                let props = element.to_mixed_property();
+                skip_rows_bytes(element.name(), &props, 0..element.count(), bytes, false)?;
+                continue;
+            }
+            if let Some(Some(projection)) = projections.get(i) {
+                read_projected_le(element, projection, bytes, budget)?;
+                continue;
+            }
+            match element {
+                #[cfg(feature = "rayon")]
+                Element::Element { name, elements } => {
+                    parallel::read_element_payload_bytes_par(name, elements, bytes, false, budget)?
+                }
+                #[cfg(not(feature = "rayon"))]
+                Element::Element { name, elements } => {
+                    read_element_payload_le_bytes(name, elements, bytes, budget)?
+                }
+                Element::ListElement { name, elements } => {
+                    read_element_payload_le_bytes(name, elements, bytes, budget)?
+                }
+                Element::MixedElement { name, elements } => {
+                    read_element_payload_le_bytes(name, elements, bytes, budget)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Payload error at the last word of input without line terminator is reported as
+/// [PLYError::UnexpectedEof], as the word may be cut in the middle
+fn unterminated_line_as_eof<L: AsciiLines>(tokens: &Tokens<L>, e: PLYError) -> PLYError {
+    match e {
+        PLYError::Payload {
+            element,
+            row,
+            offset,
+            ..
+        } if tokens.is_at_unterminated_end() => PLYError::UnexpectedEof {
+            element,
+            row,
+            offset,
+        },
+        e => e,
+    }
+}
+
+/// Report any word after the last element
+fn check_trailing_tokens<L: AsciiLines>(
+    tokens: &mut Tokens<L>,
+    warnings: &mut Warnings,
+) -> PLYResult<()> {
+    if tokens.skip_whitespace()? {
+        let offset = tokens.offset();
+        return warnings.warn(PLYWarning::TrailingData { offset });
+    }
+    Ok(())
+}
+
+/// Report any byte after the last element
+fn check_trailing_bytes<I: Iterator<Item = u8>>(
+    bytes: &mut Bytes<I>,
+    warnings: &mut Warnings,
+) -> PLYResult<()> {
+    let offset = bytes.offset();
+    match bytes.next() {
+        Some(_) => warnings.warn(PLYWarning::TrailingData { offset }),
+        None => Ok(()),
+    }
+}
+
+impl PLYHeader {
+    /// Read only header of PLY file, payload is not read.
+    ///
+    /// Compressed file is detected as [PLYFile::from_file_with_options],
+    /// `payload_offset` is then offset in decompressed data.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> PLYResult<PLYHeader> {
+        let mut reader = Decompressed::new(BufReader::new(File::open(path)?))?;
+        PLYHeader::from_buf_read(&mut reader)
+    }
+
+    /// Read only header from [BufRead].
+    ///
+    /// Header is read line by line, so `reader` is left at the start of payload.
+    pub fn from_buf_read<R: BufRead>(reader: &mut R) -> PLYResult<PLYHeader> {
+        PLYHeader::from_buf_read_with_limits(reader, &Limits::default())
+    }
+
+    /// [PLYHeader::from_buf_read] checking header lines, elements and rows against `limits`
+    pub fn from_buf_read_with_limits<R: BufRead>(
+        reader: &mut R,
+        limits: &Limits,
+    ) -> PLYResult<PLYHeader> {
+        let mut lines = BufLines::new(reader);
+        let file = read_header_with(&mut lines, &mut Warnings::default(), limits)?;
+        Ok(PLYHeader {
+            file,
+            payload_offset: lines.position(),
+        })
+    }
+}
+
+#[test]
+fn test_read_ply_file_binary_le() {
+    use crate::*;
+    let mut data = b"\
+ply
+format binary_little_endian 1.0
+element vertex 2
+property float x
+element face 1
+property list uchar int vertex_list
+end_header
+"
+    .to_vec();
+    // 0x0A (`\n`) and invalid UTF-8 bytes in payload
+    data.extend_from_slice(&f32::from_bits(0x0A0A_0A0A).to_le_bytes());
+    data.extend_from_slice(&f32::from_bits(0x3FFF_FF0A).to_le_bytes());
+    data.push(2);
+    data.extend_from_slice(&10i32.to_le_bytes());
+    data.extend_from_slice(&(-1i32).to_le_bytes());
+
+    let ply = PLYFile::from_buf_read(&mut data.as_slice()).unwrap();
+    assert_eq!(
+        ply.elements,
+        vec![
+            Element::Element {
+                name: "vertex".to_string(),
+                elements: GenericElement {
+                    count: 2,
+                    props: Property {
+                        props: vec![PLYValueTypeName::Float],
+                        names: vec!["x".to_string()],
+                        namings: vec![TypeNaming::Classic; 1],
+                    },
+                    payloads: vec![
+                        Payload(vec![PLYValue::Float(f32::from_bits(0x0A0A_0A0A))]),
+                        Payload(vec![PLYValue::Float(f32::from_bits(0x3FFF_FF0A))]),
+                    ]
+                }
+            },
+            Element::ListElement {
+                name: "face".to_string(),
+                elements: GenericElement {
+                    count: 1,
+                    props: PropertyList {
+                        count: PLYValueTypeName::Uchar,
+                        prop: PLYValueTypeName::Int,
+                        name: "vertex_list".to_string(),
+                        count_naming: TypeNaming::Classic,
+                        naming: TypeNaming::Classic,
+                    },
+                    payloads: vec![Payload(vec![PLYValue::Int(10), PLYValue::Int(-1)])]
+                }
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_read_ply_file_binary_be() {
+    use crate::*;
+    let mut data = b"\
+ply
+format binary_big_endian 1.0
+element face 1
+property list ushort short vertex_list
+end_header
+"
+    .to_vec();
+    data.extend_from_slice(&2u16.to_be_bytes());
+    data.extend_from_slice(&0x0A0Ai16.to_be_bytes());
+    data.extend_from_slice(&(-2i16).to_be_bytes());
+
+    let ply = PLYFile::from_buf_read(&mut data.as_slice()).unwrap();
+    let Element::ListElement { elements, .. } = &ply.elements[0] else {
+        panic!("face must be list element")
+    };
+    assert_eq!(
+        elements.payload(),
+        &[Payload(vec![PLYValue::Short(0x0A0A), PLYValue::Short(-2)])]
+    );
 }
 
 #[test]
@@ -95,7 +628,7 @@ end_header
 3 1 2 3
 ";
     let mut lines = data.lines().map(|s| s.to_string());
-    let ply = PLYFile::from_lines(&mut lines);
+    let ply = PLYFile::from_lines(&mut lines).unwrap();
     assert_eq!(
         ply,
         PLYFile {
@@ -103,6 +636,8 @@ end_header
                 version: "1.0".to_string()
             },
             comments: vec![Comment(vec!["test".to_string(), "data".to_string()])],
+            obj_infos: Vec::new(),
+            obj_info_positions: Vec::new(),
             elements: vec![
                 Element::Element {
                     name: "vertex".to_string(),
@@ -115,6 +650,7 @@ end_header
                                 PLYValueTypeName::Float,
                             ],
                             names: vec!["x".to_string(), "y".to_string(), "z".to_string()],
+                            namings: vec![TypeNaming::Classic; 3],
                         },
                         payloads: vec![
                             Payload(vec![
@@ -142,7 +678,9 @@ end_header
                         props: PropertyList {
                             prop: crate::PLYValueTypeName::Uint,
                             name: "vertex_list".to_string(),
-                            count: crate::PLYValueTypeName::Uchar
+                            count: crate::PLYValueTypeName::Uchar,
+                            count_naming: TypeNaming::Classic,
+                            naming: TypeNaming::Classic,
                         },
                         payloads: vec![
                             Payload(vec![PLYValue::Uint(1)]),
@@ -159,3 +697,763 @@ end_header
         }
     )
 }
+
+#[test]
+fn test_read_ply_file_truncated() {
+    use crate::error::PLYError;
+
+    let mut data = b"\
+ply
+format binary_big_endian 1.0
+element vertex 2
+property int x
+end_header
+"
+    .to_vec();
+    let header_len = data.len();
+    data.extend_from_slice(&[0, 0, 0, 1, 0, 0]);
+    assert_eq!(
+        PLYFile::from_buf_read(&mut data.as_slice()),
+        Err(PLYError::UnexpectedEof {
+            element: "vertex".to_string(),
+            row: 1,
+            offset: header_len + 6
+        })
+    );
+    assert!(matches!(
+        PLYFile::from_file("./not/exist.ply"),
+        Err(PLYError::Io(_))
+    ));
+}
+
+#[test]
+fn test_read_mixed_element_round_trip() {
+    use crate::*;
+    use std::io::BufWriter;
+
+    let face = {
+        let mut props = MixedProperty::new();
+        props.push_prop("flags", PLYValueTypeName::Short);
+        props.push_list_prop(
+            "vertex_indices",
+            PLYValueTypeName::Uchar,
+            PLYValueTypeName::Int,
+        );
+        props.push_prop("red", PLYValueTypeName::Uchar);
+        let mut element = GenericElement::new(props);
+        element
+            .push_payload(MixedPayload::new(vec![
+                MixedValue::Scalar(PLYValue::Short(-1)),
+                MixedValue::List(vec![PLYValue::Int(0), PLYValue::Int(1), PLYValue::Int(2)]),
+                MixedValue::Scalar(PLYValue::Uchar(255)),
+            ]))
+            .unwrap();
+        element
+            .push_payload(MixedPayload::new(vec![
+                MixedValue::Scalar(PLYValue::Short(7)),
+                MixedValue::List(vec![]),
+                MixedValue::Scalar(PLYValue::Uchar(10)),
+            ]))
+            .unwrap();
+        element
+    };
+    let version = "1.0".to_string();
+    for format in [
+        Format::Ascii {
+            version: version.clone(),
+        },
+        Format::BinaryBigEndian {
+            version: version.clone(),
+        },
+        Format::BinaryLittleEndian {
+            version: version.clone(),
+        },
+    ] {
+        let mut ply = PLYFile::new(format);
+        ply.elements.push(Element::MixedElement {
+            name: "face".to_string(),
+            elements: face.clone(),
+        });
+        let mut writer = BufWriter::new(Vec::new());
+        ply.write(&mut writer).unwrap();
+        let bytes = writer.into_inner().unwrap();
+        assert_eq!(PLYFile::from_buf_read(&mut bytes.as_slice()).unwrap(), ply);
+    }
+}
+
+#[test]
+fn test_read_multiple_list_properties() {
+    use crate::*;
+    let data = "\
+ply
+format ascii 1.0
+element face 2
+property list uchar int vertex_indices
+property list uchar float texcoord
+property uchar red
+end_header
+3 0 1 2 6 0 0 1 0 1 1 255
+4 0 1 2 3 0 128
+";
+    let ply = PLYFile::from_buf_read(&mut data.as_bytes()).unwrap();
+    let Element::MixedElement { elements, .. } = &ply.elements[0] else {
+        panic!("face must be mixed element")
+    };
+    assert_eq!(
+        elements
+            .list_values("vertex_indices")
+            .unwrap()
+            .map(|l| l.len())
+            .collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+    assert_eq!(
+        elements
+            .list_values("texcoord")
+            .unwrap()
+            .map(|l| l.len())
+            .collect::<Vec<_>>(),
+        vec![6, 0]
+    );
+    assert_eq!(
+        elements.scalar_values("red").unwrap().collect::<Vec<_>>(),
+        vec![PLYValue::Uchar(255), PLYValue::Uchar(128)]
+    );
+
+    for format in [
+        Format::BinaryBigEndian {
+            version: "1.0".to_string(),
+        },
+        Format::BinaryLittleEndian {
+            version: "1.0".to_string(),
+        },
+    ] {
+        let ply = PLYFile {
+            format,
+            ..ply.clone()
+        };
+        let mut writer = std::io::BufWriter::new(Vec::new());
+        ply.write(&mut writer).unwrap();
+        let bytes = writer.into_inner().unwrap();
+        assert_eq!(PLYFile::from_buf_read(&mut bytes.as_slice()).unwrap(), ply);
+    }
+}
+
+#[test]
+fn test_read_sized_type_names() {
+    use crate::*;
+    let data = "\
+ply
+format ascii 1.0
+element vertex 1
+property float32 x
+property uint8 red
+element face 1
+property list uint8 int32 vertex_indices
+end_header
+0.5 255
+3 0 1 2
+";
+    let ply = PLYFile::from_buf_read(&mut data.as_bytes()).unwrap();
+    let Element::Element { elements, .. } = &ply.elements[0] else {
+        panic!("vertex must be scalar element")
+    };
+    assert_eq!(
+        elements.property().iter().collect::<Vec<_>>(),
+        vec![
+            ("x", PLYValueTypeName::Float),
+            ("red", PLYValueTypeName::Uchar)
+        ]
+    );
+    assert_eq!(
+        elements.payload(),
+        &[Payload(vec![PLYValue::Float(0.5), PLYValue::Uchar(255)])]
+    );
+
+    // original naming is kept
+    let mut writer = std::io::BufWriter::new(Vec::new());
+    ply.write(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+        data
+    );
+
+    // naming can be chosen when writing
+    let mut writer = std::io::BufWriter::new(Vec::new());
+    ply.write_with_options(
+        &mut writer,
+        &WriteOptions {
+            type_naming: Some(TypeNaming::Classic),
+            ..WriteOptions::default()
+        },
+    )
+    .unwrap();
+    let written = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+    assert!(written.contains("property float x\nproperty uchar red\n"));
+    assert!(written.contains("property list uchar int vertex_indices\n"));
+
+    // count and value types of list keep their own naming
+    let mixed = data.replace("list uint8 int32", "list uchar int32");
+    let ply = PLYFile::from_buf_read(&mut mixed.as_bytes()).unwrap();
+    let mut writer = std::io::BufWriter::new(Vec::new());
+    ply.write(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+        mixed
+    );
+
+    // also for list of element with other properties
+    let mixed = mixed
+        .replace("vertex_indices\n", "vertex_indices\nproperty uint8 flags\n")
+        .replace("3 0 1 2\n", "3 0 1 2 1\n");
+    let ply = PLYFile::from_buf_read(&mut mixed.as_bytes()).unwrap();
+    assert!(matches!(ply.elements[1], Element::MixedElement { .. }));
+    let mut writer = std::io::BufWriter::new(Vec::new());
+    ply.write(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+        mixed
+    );
+}
+
+#[test]
+fn test_read_obj_info() {
+    use crate::*;
+    let data = "\
+ply
+format ascii 1.0
+comment scanned data
+obj_info scanner_id 42
+obj_info unit mm
+element vertex 1
+property float x
+end_header
+0.5
+";
+    let ply = PLYFile::from_buf_read(&mut data.as_bytes()).unwrap();
+    assert_eq!(
+        ply.obj_infos,
+        vec![
+            ObjInfo::from_string("scanner_id 42"),
+            ObjInfo::from_string("unit mm")
+        ]
+    );
+    assert_eq!(ply.comments, vec![Comment::from_string("scanned data")]);
+
+    let mut writer = std::io::BufWriter::new(Vec::new());
+    ply.write(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+        data
+    );
+}
+
+#[test]
+fn test_read_header_only() {
+    use crate::*;
+    let header = "\
+ply
+format binary_little_endian 1.0
+comment header only
+element vertex 2
+property float x
+end_header
+";
+    let mut data = header.as_bytes().to_vec();
+    data.extend_from_slice(&[0x0A, 0xFF, 0x00, 0x01]);
+    let mut reader = data.as_slice();
+    let header = PLYHeader::from_buf_read(&mut reader).unwrap();
+    assert_eq!(header.payload_offset, data.len() - 4);
+    assert_eq!(
+        header.file.comments,
+        vec![Comment::from_string("header only")]
+    );
+    assert_eq!(header.file.elements.len(), 1);
+    assert_eq!(header.file.elements[0].name(), "vertex");
+    assert_eq!(header.file.elements[0].count(), 2);
+    assert!(matches!(
+        &header.file.elements[0],
+        Element::Element { elements, .. } if elements.payload().is_empty()
+    ));
+    assert_eq!(reader, &[0x0A, 0xFF, 0x00, 0x01][..]);
+}
+
+#[test]
+fn test_read_from_reader() {
+    use crate::*;
+    use std::io::Read;
+    let mut data = b"\
+ply
+format binary_big_endian 1.0
+element vertex 2
+property ushort x
+end_header
+"
+    .to_vec();
+    data.extend_from_slice(&0x0A0Au16.to_be_bytes());
+    data.extend_from_slice(&0xFFFFu16.to_be_bytes());
+
+    let expected = PLYFile::from_bytes(&data).unwrap();
+    assert_eq!(
+        expected.elements[0],
+        Element::Element {
+            name: "vertex".to_string(),
+            elements: GenericElement {
+                count: 2,
+                props: Property {
+                    props: vec![PLYValueTypeName::Ushort],
+                    names: vec!["x".to_string()],
+                    namings: vec![TypeNaming::Classic],
+                },
+                payloads: vec![
+                    Payload(vec![PLYValue::Ushort(0x0A0A)]),
+                    Payload(vec![PLYValue::Ushort(0xFFFF)]),
+                ]
+            }
+        }
+    );
+
+    // reader made of two parts, split in the middle of the first header line
+    let (head, tail) = data.split_at(10);
+    let chunked = head.chain(std::io::Cursor::new(tail.to_vec()).take(u64::MAX));
+    assert_eq!(PLYFile::from_reader(chunked).unwrap(), expected);
+}
+
+#[test]
+fn test_read_selected_elements() {
+    use crate::*;
+    let ascii = "\
+ply
+format ascii 1.0
+element vertex 2
+property float x
+element face 2
+property list uchar int vertex_indices
+element edge 1
+property int vertex1
+end_header
+0.5
+1.5
+3 0 1 2
+2 not numbers
+7
+";
+    let only = |names: &[&str]| ReadOptions {
+        elements: Some(names.iter().map(|n| n.to_string()).collect()),
+        ..ReadOptions::default()
+    };
+    let ply = PLYFile::from_bytes(ascii.as_bytes());
+    assert!(matches!(ply, Err(error::PLYError::Payload { .. })));
+
+    let ply = PLYFile::from_buf_read_with_options(&mut ascii.as_bytes(), &only(&["edge"])).unwrap();
+    assert_eq!(ply.elements.len(), 1);
+    assert!(matches!(
+        &ply.elements[0],
+        Element::Element { name, elements }
+            if name == "edge" && elements.payload() == [Payload(vec![PLYValue::Int(7)])]
+    ));
+
+    // binary: list element walked by its lengths, fixed size element skipped at once
+    let mut binary = b"\
+ply
+format binary_little_endian 1.0
+element vertex 2
+property float x
+element face 2
+property list uchar int vertex_indices
+element edge 1
+property int vertex1
+end_header
+"
+    .to_vec();
+    binary.extend_from_slice(&0.5f32.to_le_bytes());
+    binary.extend_from_slice(&1.5f32.to_le_bytes());
+    binary.push(1);
+    binary.extend_from_slice(&0x0A0A_0A0Ai32.to_le_bytes());
+    binary.push(2);
+    binary.extend_from_slice(&0i32.to_le_bytes());
+    binary.extend_from_slice(&1i32.to_le_bytes());
+    binary.extend_from_slice(&7i32.to_le_bytes());
+
+    let ply =
+        PLYFile::from_buf_read_with_options(&mut binary.as_slice(), &only(&["edge"])).unwrap();
+    assert_eq!(ply.elements.len(), 1);
+    assert_eq!(ply.elements[0].name(), "edge");
+    assert!(matches!(
+        &ply.elements[0],
+        Element::Element { elements, .. } if elements.payload() == [Payload(vec![PLYValue::Int(7)])]
+    ));
+
+    // elements after the last one to load are not read, even if truncated
+    let truncated = &binary[..binary.len() - 6];
+    let ply = PLYFile::from_buf_read_with_options(&mut &truncated[..], &only(&["vertex"])).unwrap();
+    assert_eq!(ply.elements.len(), 1);
+    assert_eq!(ply.elements[0].count(), 2);
+    assert_eq!(
+        PLYFile::from_buf_read_with_options(&mut &truncated[..], &only(&["edge"])),
+        Err(error::PLYError::UnexpectedEof {
+            element: "face".to_string(),
+            row: 1,
+            offset: truncated.len() - 7,
+        })
+    );
+}
+
+#[test]
+fn test_read_projected_properties() {
+    use crate::*;
+    let header = "\
+ply
+format {format} 1.0
+element vertex 2
+property float x
+property float y
+property uchar red
+element face 1
+property list uchar int vertex_indices
+property list uchar float texcoord
+property uchar red
+end_header
+";
+    let options = ReadOptions {
+        properties: vec![
+            (
+                "vertex".to_string(),
+                vec!["red".to_string(), "x".to_string()],
+            ),
+            ("face".to_string(), vec!["vertex_indices".to_string()]),
+        ]
+        .into_iter()
+        .collect(),
+        ..ReadOptions::default()
+    };
+    let vertex_props = Property {
+        props: vec![PLYValueTypeName::Float, PLYValueTypeName::Uchar],
+        names: vec!["x".to_string(), "red".to_string()],
+        namings: vec![TypeNaming::Classic; 2],
+    };
+    let expected = vec![
+        Element::Element {
+            name: "vertex".to_string(),
+            elements: GenericElement {
+                count: 2,
+                props: vertex_props,
+                payloads: vec![
+                    Payload(vec![PLYValue::Float(0.5), PLYValue::Uchar(1)]),
+                    Payload(vec![PLYValue::Float(2.5), PLYValue::Uchar(3)]),
+                ],
+            },
+        },
+        Element::ListElement {
+            name: "face".to_string(),
+            elements: GenericElement {
+                count: 1,
+                props: PropertyList::new(
+                    "vertex_indices",
+                    PLYValueTypeName::Uchar,
+                    PLYValueTypeName::Int,
+                ),
+                payloads: vec![Payload(vec![
+                    PLYValue::Int(0),
+                    PLYValue::Int(1),
+                    PLYValue::Int(2),
+                ])],
+            },
+        },
+    ];
+
+    let ascii = header.replace("{format}", "ascii") + "0.5 -1 1\n2.5 -2 3\n3 0 1 2 2 0.25 0.75 9\n";
+    let ply = PLYFile::from_buf_read_with_options(&mut ascii.as_bytes(), &options).unwrap();
+    assert_eq!(ply.elements, expected);
+
+    let mut binary = header.replace("{format}", "binary_big_endian").into_bytes();
+    for (x, y, red) in [(0.5f32, -1f32, 1u8), (2.5, -2.0, 3)] {
+        binary.extend_from_slice(&x.to_be_bytes());
+        binary.extend_from_slice(&y.to_be_bytes());
+        binary.push(red);
+    }
+    binary.push(3);
+    for i in 0..3i32 {
+        binary.extend_from_slice(&i.to_be_bytes());
+    }
+    binary.push(2);
+    binary.extend_from_slice(&0.25f32.to_be_bytes());
+    binary.extend_from_slice(&0.75f32.to_be_bytes());
+    binary.push(9);
+    let ply = PLYFile::from_buf_read_with_options(&mut binary.as_slice(), &options).unwrap();
+    assert_eq!(ply.elements, expected);
+
+    // projected file is written with kept properties only
+    let mut writer = std::io::BufWriter::new(Vec::new());
+    ply.write(&mut writer).unwrap();
+    let written = PLYFile::from_bytes(&writer.into_inner().unwrap()).unwrap();
+    assert_eq!(written.elements, expected);
+}
+
+#[test]
+fn test_read_strict_and_lenient() {
+    use crate::error::{PLYError, PLYWarning};
+    use crate::*;
+
+    let read = |data: &[u8], mode| {
+        let options = ReadOptions {
+            mode,
+            ..ReadOptions::default()
+        };
+        PLYFile::from_buf_read_with_warnings(&mut &data[..], &options)
+    };
+
+    // values after the last row are trailing data, whichever line they are on
+    let ascii = b"\
+ply
+format ascii 1.0
+element face 2
+property list uchar int vertex_indices
+end_header
+2 0 1 1
+2
+
+3 3 3
+";
+    let (ply, warnings) = read(ascii, ParseMode::Lenient).unwrap();
+    assert_eq!(
+        ply.elements[0].count(),
+        2,
+        "rows are read as declared in header"
+    );
+    assert_eq!(warnings, vec![PLYWarning::TrailingData { offset: 97 }]);
+    assert_eq!(
+        read(ascii, ParseMode::Strict),
+        Err(PLYError::TrailingData { offset: 97 })
+    );
+
+    let mut binary = b"\
+ply
+format binary_little_endian 1.0
+element vertex 1
+property uchar x
+end_header
+"
+    .to_vec();
+    binary.push(1);
+    assert_eq!(read(&binary, ParseMode::Strict).unwrap().1, vec![]);
+    binary.push(2);
+    let offset = binary.len() - 1;
+    assert_eq!(
+        read(&binary, ParseMode::Lenient).unwrap().1,
+        vec![PLYWarning::TrailingData { offset }]
+    );
+    assert_eq!(
+        read(&binary, ParseMode::Strict),
+        Err(PLYError::TrailingData { offset })
+    );
+    assert!(PLYFile::from_bytes(&binary).is_ok());
+}
+
+#[test]
+fn test_read_recover_truncated() {
+    use crate::error::{PLYError, PLYWarning};
+    use crate::*;
+
+    let read = |data: &[u8], mode| {
+        let options = ReadOptions {
+            mode,
+            recover_truncated: true,
+            ..ReadOptions::default()
+        };
+        PLYFile::from_buf_read_with_warnings(&mut &data[..], &options)
+    };
+
+    let mut binary = b"\
+ply
+format binary_little_endian 1.0
+element vertex 3
+property short x
+element face 2
+property list uchar int vertex_indices
+end_header
+"
+    .to_vec();
+    let header_len = binary.len();
+    binary.extend_from_slice(&[1, 0, 2, 0, 3]);
+    let (ply, warnings) = read(&binary, ParseMode::Lenient).unwrap();
+    assert_eq!(
+        ply.elements[0],
+        Element::Element {
+            name: "vertex".to_string(),
+            elements: GenericElement {
+                count: 2,
+                props: Property {
+                    props: vec![PLYValueTypeName::Short],
+                    names: vec!["x".to_string()],
+                    namings: vec![TypeNaming::Classic],
+                },
+                payloads: vec![
+                    Payload(vec![PLYValue::Short(1)]),
+                    Payload(vec![PLYValue::Short(2)]),
+                ],
+            },
+        }
+    );
+    assert_eq!(ply.elements[1].count(), 0);
+    assert_eq!(
+        warnings,
+        vec![
+            PLYWarning::MissingRows {
+                element: "vertex".to_string(),
+                row: 2,
+                offset: header_len + 5,
+                missing: 1,
+            },
+            PLYWarning::MissingRows {
+                element: "face".to_string(),
+                row: 0,
+                offset: header_len + 5,
+                missing: 2,
+            },
+        ]
+    );
+    assert_eq!(
+        read(&binary, ParseMode::Strict),
+        Err(PLYError::UnexpectedEof {
+            element: "vertex".to_string(),
+            row: 2,
+            offset: header_len + 5,
+        })
+    );
+
+    let ascii = b"\
+ply
+format ascii 1.0
+element vertex 1
+property float x
+element face 3
+property list uchar int vertex_indices
+end_header
+0.5
+3 0 1 2
+3 0 1";
+    let (ply, warnings) = read(ascii, ParseMode::Lenient).unwrap();
+    assert_eq!(ply.elements[0].count(), 1);
+    assert_eq!(ply.elements[1].count(), 1);
+    // the last line is cut in the middle of row
+    assert_eq!(
+        warnings,
+        vec![PLYWarning::MissingRows {
+            element: "face".to_string(),
+            row: 1,
+            offset: ascii.len() - 5,
+            missing: 2,
+        }]
+    );
+}
+
+#[test]
+fn test_read_limits() {
+    use crate::error::{Limit, PLYError};
+    let read = |input: &[u8], limits: Limits| {
+        let options = ReadOptions {
+            limits,
+            ..ReadOptions::default()
+        };
+        PLYFile::from_buf_read_with_options(&mut &input[..], &options)
+    };
+    let exceeded = |limit, max, found| Err(PLYError::LimitExceeded { limit, max, found });
+
+    // count is not allocated up front
+    let hostile = b"ply\nformat binary_little_endian 1.0\nelement vertex 99999999999\nproperty float x\nend_header\n";
+    assert!(matches!(
+        read(hostile, Limits::default()),
+        Err(PLYError::UnexpectedEof { row: 0, .. })
+    ));
+    let limits = Limits {
+        max_rows: Some(1000),
+        ..Limits::default()
+    };
+    assert_eq!(
+        read(hostile, limits),
+        exceeded(Limit::Rows, 1000, 99999999999)
+    );
+    let limits = Limits {
+        max_header_lines: Some(4),
+        ..Limits::default()
+    };
+    assert_eq!(read(hostile, limits), exceeded(Limit::HeaderLines, 4, 5));
+
+    // long line is read only up to the limit
+    let mut long_line = b"ply\nformat ascii 1.0\ncomment ".to_vec();
+    long_line.resize(1 << 20, b'x');
+    let limits = Limits {
+        max_header_bytes: Some(64),
+        ..Limits::default()
+    };
+    assert_eq!(
+        read(&long_line, limits),
+        exceeded(Limit::HeaderBytes, 64, 65)
+    );
+
+    let mut binary = b"ply
+format binary_little_endian 1.0
+element vertex 2
+property short x
+element face 1
+property list uint uchar vertex_indices
+end_header
+"
+    .to_vec();
+    binary.extend_from_slice(&[1, 0, 2, 0]);
+    binary.extend_from_slice(&4_000_000_000u32.to_le_bytes());
+    let limits = Limits {
+        max_elements: Some(1),
+        ..Limits::default()
+    };
+    assert_eq!(read(&binary, limits), exceeded(Limit::Elements, 1, 2));
+    let limits = Limits {
+        max_list_len: Some(16),
+        ..Limits::default()
+    };
+    assert_eq!(
+        read(&binary, limits),
+        exceeded(Limit::ListLength, 16, 4_000_000_000)
+    );
+    // list of skipped element is not limited
+    let options = ReadOptions {
+        elements: Some(vec!["vertex".to_string()]),
+        limits,
+        ..ReadOptions::default()
+    };
+    assert!(PLYFile::from_buf_read_with_options(&mut &binary[..], &options).is_ok());
+
+    let ascii = b"ply
+format ascii 1.0
+element vertex 3
+property short x
+property short y
+end_header
+1 2
+3 4
+5 6
+";
+    let limits = Limits {
+        max_decoded_bytes: Some(8),
+        ..Limits::default()
+    };
+    assert_eq!(read(ascii, limits), exceeded(Limit::DecodedBytes, 8, 12));
+    let header_bytes = ascii.len() - b"1 2\n3 4\n5 6\n".len();
+    let limits = Limits {
+        max_header_bytes: Some(header_bytes - 1),
+        ..Limits::default()
+    };
+    assert_eq!(
+        read(ascii, limits),
+        exceeded(Limit::HeaderBytes, header_bytes - 1, header_bytes)
+    );
+    // payload is not limited by header size
+    let limits = Limits {
+        max_decoded_bytes: Some(12),
+        max_header_lines: Some(6),
+        max_header_bytes: Some(header_bytes),
+        ..Limits::default()
+    };
+    assert!(read(ascii, limits).is_ok());
+}
@@ -0,0 +1,504 @@
+//! Async reader for tokio (feature `async`)
+
+use std::io;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+
+use crate::{
+    error::{Limit, PLYError, PLYResult},
+    Element, Format, Limits, MixedPayload, PLYFile, PLYHeader,
+};
+
+use super::{
+    header::{header_from_lines, next_header_line},
+    limits::Budget,
+    lines::TextLines,
+    payload::{read_row_ascii, read_row_be, read_row_le, Bytes},
+    stream::Current,
+    tokens::Tokens,
+    warnings::Warnings,
+};
+
+/// Bytes read from async reader at least at once while decoding binary rows
+const MIN_READ: usize = 8 * 1024;
+
+impl PLYHeader {
+    /// Read only header from [AsyncBufRead], `reader` is left at the start of payload.
+    ///
+    /// Header lines are read and parsed one at a time as by [PLYHeader::from_buf_read],
+    /// so input which is not PLY is rejected on its first line.
+    pub async fn from_async_buf_read<R: AsyncBufRead + Unpin>(
+        reader: &mut R,
+    ) -> PLYResult<PLYHeader> {
+        PLYHeader::from_async_buf_read_with_limits(reader, &Limits::default()).await
+    }
+
+    /// [PLYHeader::from_async_buf_read] checking header lines, elements and rows against `limits`
+    pub async fn from_async_buf_read_with_limits<R: AsyncBufRead + Unpin>(
+        reader: &mut R,
+        limits: &Limits,
+    ) -> PLYResult<PLYHeader> {
+        let mut header_lines = Vec::new();
+        let mut warnings = Warnings::default();
+        let mut line = Vec::new();
+        let mut payload_offset = 0;
+        loop {
+            line.clear();
+            let len = match limits.max_header_bytes {
+                None => reader.read_until(b'\n', &mut line).await?,
+                Some(max) => {
+                    // a byte over `max` is read to tell line ending at `max` from longer one
+                    let room = max.saturating_sub(payload_offset).saturating_add(1);
+                    let len = (&mut *reader)
+                        .take(room as u64)
+                        .read_until(b'\n', &mut line)
+                        .await?;
+                    if payload_offset + len > max {
+                        return Err(PLYError::LimitExceeded {
+                            limit: Limit::HeaderBytes,
+                            max,
+                            found: payload_offset + len,
+                        });
+                    }
+                    len
+                }
+            };
+            let n = header_lines.len() + 1;
+            if len == 0 {
+                return Err(PLYError::header_syntax(
+                    n,
+                    "",
+                    "unexpected end of file, end_header not found",
+                ));
+            }
+            payload_offset += len;
+            let text = std::str::from_utf8(&line)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+                .trim_end_matches(['\n', '\r']);
+            let header_line = next_header_line(n, text, &mut warnings, limits.max_header_lines)?;
+            if header_line.is_end_header() {
+                break;
+            }
+            header_lines.push(header_line);
+        }
+        let file = header_from_lines(header_lines, &mut warnings, limits)?;
+        Ok(PLYHeader {
+            file,
+            payload_offset,
+        })
+    }
+}
+
+/// Async counterpart of [PLYReader](crate::PLYReader)
+///
+/// Rows are decoded by the same code as the synchronous reader.
+pub struct AsyncPLYReader<R> {
+    reader: R,
+    header: PLYFile,
+    /// byte offset of `buf[pos]`, or of `text` for ascii
+    offset: usize,
+    /// bytes read but not decoded yet (binary)
+    buf: Vec<u8>,
+    pos: usize,
+    eof: bool,
+    /// lines read but not parsed yet (ascii)
+    text: String,
+    current: Option<Current>,
+    next: usize,
+    budget: Budget,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncPLYReader<R> {
+    /// Read header from `reader`, payload is not read yet
+    pub async fn new(reader: R) -> PLYResult<Self> {
+        AsyncPLYReader::with_limits(reader, Limits::default()).await
+    }
+
+    /// Read header from `reader` checking it against `limits`, payload is not read yet
+    ///
+    /// Rows are checked as by [PLYReader::with_limits](crate::PLYReader::with_limits),
+    /// but rows skipped by [AsyncPLYReader::next_element] are decoded and counted too.
+    pub async fn with_limits(mut reader: R, limits: Limits) -> PLYResult<Self> {
+        let PLYHeader {
+            file,
+            payload_offset,
+        } = PLYHeader::from_async_buf_read_with_limits(&mut reader, &limits).await?;
+        Ok(Self {
+            reader,
+            header: file,
+            offset: payload_offset,
+            buf: Vec::new(),
+            pos: 0,
+            eof: false,
+            text: String::new(),
+            current: None,
+            next: 0,
+            budget: Budget::new(limits),
+        })
+    }
+
+    /// Returns parsed header
+    ///
+    /// Elements have counts and properties declared in header, but no payload.
+    pub fn header(&self) -> &PLYFile {
+        &self.header
+    }
+
+    /// Move to the next element and returns it (as declared in header), or None after the last
+    ///
+    /// Rows of the previous element which were not read are skipped.
+    pub async fn next_element(&mut self) -> PLYResult<Option<&Element>> {
+        while self.next_row().await?.is_some() {}
+        if self.next >= self.header.elements.len() {
+            self.current = None;
+            return Ok(None);
+        }
+        let element = &self.header.elements[self.next];
+        self.current = Some(Current {
+            index: self.next,
+            props: element.to_mixed_property(),
+            row: 0,
+        });
+        self.next += 1;
+        Ok(Some(element))
+    }
+
+    /// Read next row of current element, or None after its last row
+    pub async fn next_row(&mut self) -> PLYResult<Option<MixedPayload>> {
+        let (index, row) = match &self.current {
+            Some(current) if current.row < self.header.elements[current.index].count() => {
+                (current.index, current.row)
+            }
+            _ => return Ok(None),
+        };
+        let payload = match self.header.format {
+            Format::Ascii { .. } => self.read_ascii_row(index, row).await?,
+            _ => self.read_binary_row(index, row).await?,
+        };
+        self.budget.decode(&payload)?;
+        if let Some(current) = &mut self.current {
+            current.row += 1;
+        }
+        Ok(Some(payload))
+    }
+
+    /// Parse row from buffered lines, reading more lines while the row is incomplete
+    ///
+    /// Row is parsed again only after the number of buffered words doubled,
+    /// so a row wrapped over many lines takes linear time.
+    async fn read_ascii_row(&mut self, index: usize, row: usize) -> PLYResult<MixedPayload> {
+        // words buffered when the row was last parsed, counted on the first retry
+        let mut words = None;
+        loop {
+            let current = self.current.as_ref().expect("current element");
+            let name = self.header.elements[index].name();
+            let (result, consumed) = {
+                let mut tokens = Tokens::new(TextLines::new(&self.text, self.offset));
+                let max_list_len = self.budget.max_list_len();
+                let result = read_row_ascii(name, &current.props, row, &mut tokens, max_list_len);
+                (result, tokens.offset() - self.offset)
+            };
+            match result {
+                Ok(payload) => {
+                    self.text.drain(..consumed);
+                    self.offset += consumed;
+                    return Ok(payload);
+                }
+                Err(PLYError::UnexpectedEof { .. }) if !self.eof => {
+                    let mut buffered = words.unwrap_or_else(|| count_words(&self.text));
+                    let target = 2 * buffered.max(1);
+                    while buffered < target {
+                        let start = self.text.len();
+                        if self.reader.read_line(&mut self.text).await? == 0 {
+                            self.eof = true;
+                            break;
+                        }
+                        buffered += count_words(&self.text[start..]);
+                    }
+                    words = Some(buffered);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Decode row from buffered bytes, reading more while the row is incomplete
+    async fn read_binary_row(&mut self, index: usize, row: usize) -> PLYResult<MixedPayload> {
+        loop {
+            let current = self.current.as_ref().expect("current element");
+            let name = self.header.elements[index].name();
+            let max_list_len = self.budget.max_list_len();
+            let mut bytes = Bytes::new(self.buf[self.pos..].iter().copied(), self.offset);
+            let result = match self.header.format {
+                Format::BinaryBigEndian { .. } => {
+                    read_row_be(name, &current.props, row, &mut bytes, max_list_len)
+                }
+                _ => read_row_le(name, &current.props, row, &mut bytes, max_list_len),
+            };
+            match result {
+                Ok(payload) => {
+                    let consumed = bytes.offset() - self.offset;
+                    self.pos += consumed;
+                    self.offset += consumed;
+                    return Ok(payload);
+                }
+                Err(PLYError::UnexpectedEof { .. }) if !self.eof => {
+                    let pending = self.buf.len() - self.pos;
+                    self.fill(MIN_READ.max(2 * pending)).await?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Read until `len` bytes are pending or input ended
+    async fn fill(&mut self, len: usize) -> PLYResult<()> {
+        self.buf.drain(..self.pos);
+        self.pos = 0;
+        while self.buf.len() < len {
+            let chunk = self.reader.fill_buf().await?;
+            if chunk.is_empty() {
+                self.eof = true;
+                break;
+            }
+            let n = chunk.len();
+            self.buf.extend_from_slice(chunk);
+            self.reader.consume(n);
+        }
+        Ok(())
+    }
+}
+
+fn count_words(text: &str) -> usize {
+    text.split_ascii_whitespace().count()
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::io::AsyncBufRead;
+
+    use super::AsyncPLYReader;
+    use crate::{
+        error::{Limit, PLYError, PLYResult},
+        Limits, MixedPayload, MixedValue, PLYHeader, PLYReader, PLYValue,
+    };
+
+    /// Rows of all elements read by [AsyncPLYReader], with element name
+    async fn read_all<R: AsyncBufRead + Unpin>(
+        reader: &mut AsyncPLYReader<R>,
+    ) -> PLYResult<Vec<(String, Vec<MixedPayload>)>> {
+        let mut elements = Vec::new();
+        while let Some(element) = reader.next_element().await? {
+            let name = element.name().to_string();
+            let mut rows = Vec::new();
+            while let Some(row) = reader.next_row().await? {
+                rows.push(row);
+            }
+            elements.push((name, rows));
+        }
+        Ok(elements)
+    }
+
+    fn binary_le(faces: usize) -> Vec<u8> {
+        let mut data = format!(
+            "ply\r\nformat binary_little_endian 1.0\nelement vertex 1\nproperty uchar x\n\
+             element face {faces}\nproperty list uint int vertex_indices\nend_header\n"
+        )
+        .into_bytes();
+        data.push(0x0A);
+        for i in 0..faces {
+            // rows larger than a single read
+            data.extend_from_slice(&(5000u32 + i as u32).to_le_bytes());
+            for v in 0..5000 + i as i32 {
+                data.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        data
+    }
+
+    /// Rows read by synchronous [PLYReader]
+    fn read_sync(data: &[u8]) -> Vec<(String, Vec<MixedPayload>)> {
+        let mut reader = PLYReader::new(data).unwrap();
+        let mut elements = Vec::new();
+        while let Some(element) = reader.next_element().unwrap() {
+            let name = element.name().to_string();
+            elements.push((name, element.map(Result::unwrap).collect()));
+        }
+        elements
+    }
+
+    #[tokio::test]
+    async fn test_async_header() {
+        let data = binary_le(2);
+        let mut reader = &data[..];
+        let header = PLYHeader::from_async_buf_read(&mut reader).await.unwrap();
+        assert_eq!(header, PLYHeader::from_buf_read(&mut &data[..]).unwrap());
+        assert_eq!(reader.len(), data.len() - header.payload_offset);
+
+        // rejected on the first line, the rest is not read
+        let data = b"solid cube\nfacet normal 0 0 1\n";
+        let mut reader = &data[..];
+        assert_eq!(
+            PLYHeader::from_async_buf_read(&mut reader).await,
+            Err(PLYError::header_syntax(
+                1,
+                "solid cube",
+                "first line, magic number must be ply"
+            ))
+        );
+        assert_eq!(reader, b"facet normal 0 0 1\n");
+
+        let limits = Limits {
+            max_header_lines: Some(3),
+            ..Limits::default()
+        };
+        assert_eq!(
+            PLYHeader::from_async_buf_read_with_limits(&mut &binary_le(1)[..], &limits)
+                .await
+                .err(),
+            Some(PLYError::LimitExceeded {
+                limit: Limit::HeaderLines,
+                max: 3,
+                found: 4,
+            })
+        );
+
+        let mut long_line = b"ply\nformat ascii 1.0\ncomment ".to_vec();
+        long_line.resize(1 << 20, b'x');
+        let limits = Limits {
+            max_header_bytes: Some(64),
+            ..Limits::default()
+        };
+        assert_eq!(
+            PLYHeader::from_async_buf_read_with_limits(&mut &long_line[..], &limits)
+                .await
+                .err(),
+            Some(PLYError::LimitExceeded {
+                limit: Limit::HeaderBytes,
+                max: 64,
+                found: 65,
+            })
+        );
+        let header = PLYHeader::from_async_buf_read(&mut &binary_le(1)[..])
+            .await
+            .unwrap();
+        let limits = Limits {
+            max_header_bytes: Some(header.payload_offset),
+            ..Limits::default()
+        };
+        assert!(
+            PLYHeader::from_async_buf_read_with_limits(&mut &binary_le(1)[..], &limits)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_rows_binary() {
+        let data = binary_le(3);
+        let mut reader = AsyncPLYReader::new(&data[..]).await.unwrap();
+        assert_eq!(reader.header().elements.len(), 2);
+        let elements = read_all(&mut reader).await.unwrap();
+        assert_eq!(elements, read_sync(&data));
+        assert_eq!(
+            elements[0].1,
+            vec![MixedPayload::new(vec![MixedValue::Scalar(
+                PLYValue::Uchar(0x0A)
+            )])]
+        );
+
+        let truncated = &data[..data.len() - 2];
+        let mut reader = AsyncPLYReader::new(truncated).await.unwrap();
+        assert_eq!(
+            read_all(&mut reader).await,
+            Err(PLYError::UnexpectedEof {
+                element: "face".to_string(),
+                row: 2,
+                offset: truncated.len(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_rows_ascii() {
+        let data = "\
+ply
+format ascii 1.0
+element vertex 2
+property float x
+element face 2
+property list uchar int vertex_indices
+property uchar red
+end_header
+0.5
+1.5
+3 0 1 2 255 4 0 1
+2 3\r
+ 255
+";
+        let mut reader = AsyncPLYReader::new(data.as_bytes()).await.unwrap();
+        assert_eq!(
+            read_all(&mut reader).await.unwrap(),
+            read_sync(data.as_bytes())
+        );
+
+        // unread rows are skipped
+        let mut reader = AsyncPLYReader::new(data.as_bytes()).await.unwrap();
+        reader.next_element().await.unwrap();
+        assert_eq!(
+            reader.next_element().await.unwrap().map(|e| e.name()),
+            Some("face")
+        );
+        assert!(reader.next_row().await.unwrap().is_some());
+        assert!(reader.next_element().await.unwrap().is_none());
+
+        // list wrapped over a line per value
+        let mut data = "ply\nformat ascii 1.0\nelement face 2\n\
+                        property list ushort int vertex_indices\nend_header\n3000\n"
+            .to_string();
+        for i in 0..3000 {
+            data.push_str(&format!("{i}\n"));
+        }
+        data.push_str("1 7\n");
+        let mut reader = AsyncPLYReader::new(data.as_bytes()).await.unwrap();
+        assert_eq!(
+            read_all(&mut reader).await.unwrap(),
+            read_sync(data.as_bytes())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_limits() {
+        let data = binary_le(2);
+        let limits = Limits {
+            max_rows: Some(1),
+            ..Limits::default()
+        };
+        assert_eq!(
+            PLYHeader::from_async_buf_read_with_limits(&mut &data[..], &limits)
+                .await
+                .err(),
+            Some(PLYError::LimitExceeded {
+                limit: Limit::Rows,
+                max: 1,
+                found: 2,
+            })
+        );
+
+        let limits = Limits {
+            max_list_len: Some(5000),
+            ..Limits::default()
+        };
+        let mut reader = AsyncPLYReader::with_limits(&data[..], limits)
+            .await
+            .unwrap();
+        assert_eq!(
+            read_all(&mut reader).await,
+            Err(PLYError::LimitExceeded {
+                limit: Limit::ListLength,
+                max: 5000,
+                found: 5001,
+            })
+        );
+    }
+}
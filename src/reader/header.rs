@@ -1,30 +1,95 @@
 use crate::{
-    Comment, Element, Format, GenericElement, PLYFile, PLYValueTypeName, Property, PropertyList,
+    error::{Limit, PLYError, PLYResult, PLYWarning},
+    Comment, Element, Format, GenericElement, MixedProperty, ObjInfo, PLYFile, PLYValueTypeName,
+    Property, PropertyKind, PropertyList, TypeNaming, END_HEADER, MAGIC_NUMBER,
 };
 use std::convert::AsRef;
-use std::str::FromStr;
+use std::fmt::Display;
+
+use super::{
+    limits::{check, Limits},
+    lines::AsciiLines,
+    warnings::Warnings,
+};
+
+/// Read header from lines, until `end_header` line
+pub(crate) fn read_header<L: AsciiLines>(lines: &mut L) -> PLYResult<PLYFile> {
+    read_header_with(lines, &mut Warnings::default(), &Limits::default())
+}
+
+/// Read header, reporting ignored lines and unsupported version to `warnings`
+///
+/// Header lines, elements and rows are checked against `limits`.
+pub(crate) fn read_header_with<L: AsciiLines>(
+    lines: &mut L,
+    warnings: &mut Warnings,
+    limits: &Limits,
+) -> PLYResult<PLYFile> {
+    let header_lines = read_header_lines(lines, warnings, limits)?;
+    header_from_lines(header_lines, warnings, limits)
+}
+
+/// Build header from lines read by [read_header_lines], see [read_header_with]
+pub(crate) fn header_from_lines(
+    header_lines: Vec<HeaderLine>,
+    warnings: &mut Warnings,
+    limits: &Limits,
+) -> PLYResult<PLYFile> {
+    let file = from_header_lines(&mut header_lines.into_iter())?;
+    limits.check_header(&file.elements)?;
+    if file.format.version() != SUPPORTED_VERSION {
+        warnings.warn(PLYWarning::UnsupportedVersion {
+            version: file.format.version().to_string(),
+        })?;
+    }
+    Ok(file)
+}
+
+/// The only version of PLY format
+const SUPPORTED_VERSION: &str = "1.0";
 
 /// Read [HeaderLine]s
-pub(crate) fn from_header_lines<I: Iterator<Item = HeaderLine>>(lines: &mut I) -> PLYFile {
+///
+/// Lines are expected to start from the first line of file, line numbers of errors count from it.
+pub(crate) fn from_header_lines<I: Iterator<Item = HeaderLine>>(
+    lines: &mut I,
+) -> PLYResult<PLYFile> {
+    let mut lines = lines.enumerate().map(|(i, line)| (i + 1, line));
+
     // assert magic number
-    assert_eq!(
-        lines
-            .next()
-            .expect("Not found: first line, magic number must be ply"),
-        HeaderLine::FileIdentifierLine
-    );
+    match lines.next() {
+        Some((_, HeaderLine::FileIdentifierLine)) => {}
+        Some((n, line)) => {
+            return Err(PLYError::header_syntax(
+                n,
+                line.to_string(),
+                "first line, magic number must be ply",
+            ))
+        }
+        None => return Err(PLYError::header_syntax(1, "", "magic number not found")),
+    }
 
     // read format of PLY file
-    let Some(HeaderLine::FormatLine(format)) = lines.next() else {
-         panic!("Not found: secound line, format style")
+    let format = match lines.next() {
+        Some((_, HeaderLine::FormatLine(format))) => format,
+        Some((n, line)) => {
+            return Err(PLYError::header_syntax(
+                n,
+                line.to_string(),
+                "second line must be format line",
+            ))
+        }
+        None => return Err(PLYError::header_syntax(2, "", "format line not found")),
     };
 
-    // read comment and element
+    // read comment, obj_info and element
     let mut comments = Vec::new();
+    let mut obj_infos = Vec::new();
     let mut elements = Vec::new();
-    while let Some(mut next) = read_to_element_line(lines, &mut comments) {
+    while let Some(mut next) = read_to_element_line(&mut lines, &mut comments, &mut obj_infos)? {
         loop {
-            let (element, next_option) = read_element_props(lines, &mut comments, next);
+            let (element, next_option) =
+                read_element_props(&mut lines, &mut comments, &mut obj_infos, next)?;
             elements.push(element);
             if let Some(next_some) = next_option {
                 next = next_some;
@@ -34,11 +99,14 @@ pub(crate) fn from_header_lines<I: Iterator<Item = HeaderLine>>(lines: &mut I) -
         }
     }
 
-    PLYFile {
+    let (obj_info_positions, obj_infos) = obj_infos.into_iter().unzip();
+    Ok(PLYFile {
         format,
         comments,
+        obj_infos,
+        obj_info_positions,
         elements,
-    }
+    })
 }
 
 #[test]
@@ -56,17 +124,20 @@ fn test_from_header_lines() {
         HeaderLine::PropertyLine {
             prop_type: PLYValueTypeName::Float,
             name: "x".to_string(),
+            naming: TypeNaming::Classic,
         },
         HeaderLine::PropertyLine {
             prop_type: PLYValueTypeName::Float,
             name: "y".to_string(),
+            naming: TypeNaming::Classic,
         },
         HeaderLine::PropertyLine {
             prop_type: PLYValueTypeName::Float,
             name: "z".to_string(),
+            naming: TypeNaming::Classic,
         },
     ];
-    let ply_file = from_header_lines(&mut lines.into_iter());
+    let ply_file = from_header_lines(&mut lines.into_iter()).unwrap();
     assert_eq!(
         ply_file,
         PLYFile {
@@ -74,6 +145,8 @@ fn test_from_header_lines() {
                 version: "1.0".to_string()
             },
             comments: vec![Comment(vec!["test".to_string(), "data".to_string()])],
+            obj_infos: Vec::new(),
+            obj_info_positions: Vec::new(),
             elements: vec![Element::Element {
                 name: "vertex".to_string(),
                 elements: GenericElement {
@@ -85,6 +158,7 @@ fn test_from_header_lines() {
                             PLYValueTypeName::Float,
                             PLYValueTypeName::Float
                         ],
+                        namings: vec![TypeNaming::Classic; 3],
                     },
                     payloads: Vec::with_capacity(8)
                 }
@@ -96,26 +170,37 @@ fn test_from_header_lines() {
 /// Read headers for find line `element (name) (count)`
 ///
 /// Return Some((name, count)) if found, None otherwise
-fn read_to_element_line<I: Iterator<Item = HeaderLine>>(
+fn read_to_element_line<I: Iterator<Item = (usize, HeaderLine)>>(
     lines: &mut I,
     comments: &mut Vec<Comment>,
-) -> Option<(String, usize)> {
-    for line in lines {
+    obj_infos: &mut Vec<(usize, ObjInfo)>,
+) -> PLYResult<Option<(String, usize)>> {
+    for (n, line) in lines {
         match line {
             HeaderLine::ElementLine { name, count } => {
-                return Some((name, count));
+                return Ok(Some((name, count)));
             }
             HeaderLine::CommentLine(c) => comments.push(c),
+            HeaderLine::ObjInfoLine(o) => obj_infos.push((comments.len(), o)),
             HeaderLine::EmptyLine => { /* do nothing */ }
             HeaderLine::UnknownLine(_) => { /* do nothing */ }
-            HeaderLine::PropertyLine { .. } => panic!("keyword \"propety\" cannnot use here"),
-            HeaderLine::PropertyListLine(_) => panic!("keyword \"propety list\" cannnot use here"),
-            HeaderLine::FormatLine(_) => panic!("keyword \"format\" cannnot use here"),
-            HeaderLine::FileIdentifierLine => panic!("keyword \"ply\" cannnot use here"),
-            HeaderLine::EndHeader => panic!("keyword \"end_header\" is not allowed here"),
+            HeaderLine::PropertyLine { .. } | HeaderLine::PropertyListLine(_) => {
+                return Err(PLYError::header_syntax(
+                    n,
+                    line.to_string(),
+                    "keyword \"property\" cannot be used before element",
+                ))
+            }
+            HeaderLine::FormatLine(_) | HeaderLine::FileIdentifierLine | HeaderLine::EndHeader => {
+                return Err(PLYError::header_syntax(
+                    n,
+                    line.to_string(),
+                    "keyword is not allowed here",
+                ))
+            }
         }
     }
-    None
+    Ok(None)
 }
 
 #[test]
@@ -132,9 +217,10 @@ fn test_read_to_element_line() {
             count: 8,
         },
     ];
-    let mut iter = lines.into_iter();
+    let mut iter = lines.into_iter().enumerate();
     let mut comments = Vec::new();
-    let next = read_to_element_line(&mut iter, &mut comments);
+    let mut obj_infos = Vec::new();
+    let next = read_to_element_line(&mut iter, &mut comments, &mut obj_infos).unwrap();
     assert_eq!(
         comments,
         vec![Comment(vec![
@@ -150,79 +236,118 @@ fn test_read_to_element_line() {
 /// Read element's props, arg `(name, count)` is a name and count of element.
 ///
 /// Return Element and name, usize if they found while reading props.
-fn read_element_props<I: Iterator<Item = HeaderLine>>(
+fn read_element_props<I: Iterator<Item = (usize, HeaderLine)>>(
     lines: &mut I,
     comments: &mut Vec<Comment>,
+    obj_infos: &mut Vec<(usize, ObjInfo)>,
     (name, count): (String, usize),
-) -> (Element, Option<(String, usize)>) {
-    let mut prop = Property {
-        props: Vec::new(),
-        names: Vec::new(),
-    };
-    for line in lines {
+) -> PLYResult<(Element, Option<(String, usize)>)> {
+    let mut props = MixedProperty::new();
+    for (n, line) in lines {
         match line {
-            HeaderLine::PropertyLine { name, prop_type } => {
-                prop.props.push(prop_type);
-                prop.names.push(name);
-            }
-            HeaderLine::PropertyListLine(prop_list) => {
-                assert!(
-                    prop.props.is_empty(),
-                    "\"property\" and \"property lines\" cannot be used at same element"
-                );
-                return (
-                    Element::ListElement {
-                        name,
-                        elements: GenericElement {
-                            count,
-                            props: prop_list,
-                            payloads: Vec::with_capacity(count),
-                        },
-                    },
-                    None,
-                );
-            }
+            HeaderLine::PropertyLine {
+                name,
+                prop_type,
+                naming,
+            } => props.push_kind(
+                name,
+                PropertyKind::Scalar(prop_type),
+                naming,
+                TypeNaming::Classic,
+            ),
+            HeaderLine::PropertyListLine(PropertyList {
+                count,
+                prop,
+                name,
+                count_naming,
+                naming,
+            }) => props.push_kind(
+                name,
+                PropertyKind::List { count, prop },
+                naming,
+                count_naming,
+            ),
             HeaderLine::ElementLine {
                 name: next_name,
                 count: next_count,
             } => {
-                let element = Element::Element {
-                    name,
-                    elements: {
-                        GenericElement {
-                            count,
-                            props: prop,
-                            payloads: Vec::with_capacity(count),
-                        }
-                    },
-                };
-                return (element, Some((next_name, next_count)));
+                let element = new_element(name, count, props);
+                return Ok((element, Some((next_name, next_count))));
             }
             HeaderLine::CommentLine(c) => comments.push(c),
+            HeaderLine::ObjInfoLine(o) => obj_infos.push((comments.len(), o)),
             HeaderLine::EmptyLine => { /* do nothing */ }
-            HeaderLine::FileIdentifierLine => {
-                panic!("line \"ply\" is not allowed here")
-            }
-            HeaderLine::FormatLine(_) => {
-                panic!("keyword \"format\" is not allowed here")
-            }
-            HeaderLine::EndHeader => {
-                panic!("keyword \"end_header\" is not allowed here")
+            HeaderLine::FileIdentifierLine | HeaderLine::FormatLine(_) | HeaderLine::EndHeader => {
+                return Err(PLYError::header_syntax(
+                    n,
+                    line.to_string(),
+                    "keyword is not allowed here",
+                ))
             }
             HeaderLine::UnknownLine(_) => {}
         }
     }
 
-    let element = Element::Element {
-        name,
-        elements: GenericElement {
-            count,
-            props: prop,
-            payloads: Vec::with_capacity(count),
-        },
-    };
+    Ok((new_element(name, count, props), None))
+}
 
-    (element, None)
+/// Create element with the most specific property type.
+///
+/// Scalar only element is [Element::Element], single list element is [Element::ListElement],
+/// and [Element::MixedElement] otherwise.
+/// Payload is not allocated for `count` rows, as count of untrusted header may be huge.
+pub(crate) fn new_element(name: String, count: usize, mut props: MixedProperty) -> Element {
+    if props.kinds.iter().all(|kind| !kind.is_list()) {
+        let props = Property {
+            props: props
+                .kinds
+                .iter()
+                .filter_map(|kind| match kind {
+                    PropertyKind::Scalar(t) => Some(*t),
+                    PropertyKind::List { .. } => None,
+                })
+                .collect(),
+            names: props.names,
+            namings: props.namings,
+        };
+        Element::Element {
+            name,
+            elements: GenericElement {
+                count,
+                props,
+                payloads: Vec::new(),
+            },
+        }
+    } else if let [PropertyKind::List {
+        count: count_type,
+        prop,
+    }] = props.kinds[..]
+    {
+        let props = PropertyList {
+            count: count_type,
+            prop,
+            name: props.names.pop().unwrap_or_default(),
+            count_naming: props.count_namings.pop().unwrap_or_default(),
+            naming: props.namings.pop().unwrap_or_default(),
+        };
+        Element::ListElement {
+            name,
+            elements: GenericElement {
+                count,
+                props,
+                payloads: Vec::new(),
+            },
+        }
+    } else {
+        Element::MixedElement {
+            name,
+            elements: GenericElement {
+                count,
+                props,
+                payloads: Vec::new(),
+            },
+        }
+    }
 }
 
 #[test]
@@ -236,10 +361,17 @@ property uchar red
 property uchar green
 property uchar blue"
         .lines()
-        .map(parse_header_line);
+        .map(|line| parse_header_line(line).unwrap().0)
+        .enumerate();
     let mut comments = Vec::new();
 
-    let (element, next) = read_element_props(&mut lines, &mut comments, ("vertex".to_string(), 20));
+    let (element, next) = read_element_props(
+        &mut lines,
+        &mut comments,
+        &mut Vec::new(),
+        ("vertex".to_string(), 20),
+    )
+    .unwrap();
 
     assert_eq!(
         element,
@@ -263,7 +395,8 @@ property uchar blue"
                         "red".to_string(),
                         "green".to_string(),
                         "blue".to_string()
-                    ]
+                    ],
+                    namings: vec![TypeNaming::Classic; 6],
                 },
                 payloads: Vec::with_capacity(20),
             }
@@ -273,17 +406,122 @@ property uchar blue"
     assert_eq!(comments, vec![Comment(vec!["color".to_string()])]);
 }
 
-/// Read from str iter for end_header line
+#[test]
+fn test_read_mixed_element_props() {
+    let mut lines = "\
+property list uchar int vertex_indices
+property uchar red
+property uchar green
+property uchar blue
+element edge 1"
+        .lines()
+        .map(|line| parse_header_line(line).unwrap().0)
+        .enumerate();
+    let mut comments = Vec::new();
+
+    let (element, next) = read_element_props(
+        &mut lines,
+        &mut comments,
+        &mut Vec::new(),
+        ("face".to_string(), 2),
+    )
+    .unwrap();
+
+    assert_eq!(
+        element,
+        Element::MixedElement {
+            name: "face".to_string(),
+            elements: GenericElement {
+                count: 2,
+                props: MixedProperty {
+                    kinds: vec![
+                        PropertyKind::List {
+                            count: PLYValueTypeName::Uchar,
+                            prop: PLYValueTypeName::Int
+                        },
+                        PropertyKind::Scalar(PLYValueTypeName::Uchar),
+                        PropertyKind::Scalar(PLYValueTypeName::Uchar),
+                        PropertyKind::Scalar(PLYValueTypeName::Uchar),
+                    ],
+                    names: vec![
+                        "vertex_indices".to_string(),
+                        "red".to_string(),
+                        "green".to_string(),
+                        "blue".to_string()
+                    ],
+                    namings: vec![TypeNaming::Classic; 4],
+                    count_namings: vec![TypeNaming::Classic; 4],
+                },
+                payloads: Vec::with_capacity(2),
+            }
+        }
+    );
+    assert_eq!(next, Some(("edge".to_string(), 1)));
+}
+
+/// Read lines for end_header line, up to `max_lines` lines
 /// EndHeader is not member of header lines
-pub(crate) fn read_header_lines<I: Iterator<Item = String>>(lines: &mut I) -> Vec<HeaderLine> {
-    lines
-        .map(parse_header_line)
-        .take_while(|line| !line.is_end_header())
-        .collect::<Vec<_>>()
+pub(crate) fn read_header_lines<L: AsciiLines>(
+    lines: &mut L,
+    warnings: &mut Warnings,
+    limits: &Limits,
+) -> PLYResult<Vec<HeaderLine>> {
+    let mut header_lines = Vec::new();
+    lines.limit_header_bytes(limits.max_header_bytes);
+    while let Some(line) = lines.next_line()? {
+        let n = header_lines.len() + 1;
+        let header_line = next_header_line(n, line, warnings, limits.max_header_lines)?;
+        if header_line.is_end_header() {
+            lines.limit_header_bytes(None);
+            return Ok(header_lines);
+        }
+        header_lines.push(header_line);
+    }
+    Err(PLYError::header_syntax(
+        header_lines.len() + 1,
+        "",
+        "unexpected end of file, end_header not found",
+    ))
+}
+
+/// Parse `n`th header line, checking the number of lines against `max_lines`
+///
+/// The first line must be the magic number, so input which is not PLY is rejected on its first line.
+/// Lines which are not well-formed, but can be read, are reported to `warnings`.
+pub(crate) fn next_header_line(
+    n: usize,
+    line: &str,
+    warnings: &mut Warnings,
+    max_lines: Option<usize>,
+) -> PLYResult<HeaderLine> {
+    check(Limit::HeaderLines, max_lines, n)?;
+    let (header_line, extra) =
+        parse_header_line(line).map_err(|reason| PLYError::header_syntax(n, line, reason))?;
+    if n == 1 && header_line != HeaderLine::FileIdentifierLine {
+        return Err(PLYError::header_syntax(
+            n,
+            line,
+            "first line, magic number must be ply",
+        ));
+    }
+    match &header_line {
+        HeaderLine::UnknownLine(keyword) => warnings.warn(PLYWarning::UnknownKeyword {
+            line: n,
+            keyword: keyword.clone(),
+        })?,
+        HeaderLine::EmptyLine => warnings.warn(PLYWarning::EmptyHeaderLine { line: n })?,
+        _ => {}
+    }
+    if let Some(words) = extra {
+        warnings.warn(PLYWarning::ExtraWords { line: n, words })?;
+    }
+    Ok(header_line)
 }
 
 #[test]
 fn test_read_header_lines() {
+    use super::lines::StrLines;
+
     let mut input = "\
 ply
 format ascii 1.0
@@ -304,7 +542,12 @@ end_header
 "
     .lines()
     .map(|e| e.to_string());
-    let header_lines = read_header_lines(&mut input);
+    let header_lines = read_header_lines(
+        &mut StrLines::new(&mut input),
+        &mut Warnings::default(),
+        &Limits::default(),
+    )
+    .unwrap();
     assert_eq!(
         header_lines,
         vec![
@@ -319,15 +562,18 @@ end_header
             },
             HeaderLine::PropertyLine {
                 prop_type: PLYValueTypeName::Float,
-                name: "x".to_string()
+                name: "x".to_string(),
+                naming: TypeNaming::Classic,
             },
             HeaderLine::PropertyLine {
                 prop_type: PLYValueTypeName::Float,
-                name: "y".to_string()
+                name: "y".to_string(),
+                naming: TypeNaming::Classic,
             },
             HeaderLine::PropertyLine {
                 prop_type: PLYValueTypeName::Float,
-                name: "z".to_string()
+                name: "z".to_string(),
+                naming: TypeNaming::Classic,
             },
             // HeaderLine::EndHeader
             // EndHeader is not member of header lines
@@ -344,6 +590,145 @@ end_header
     assert_eq!(input.next(), None);
 }
 
+#[test]
+fn test_read_header_lines_buf() {
+    use super::lines::BufLines;
+
+    let mut input: &[u8] = b"ply\r\nformat binary_little_endian 1.0\r\nend_header\r\n\n\xff";
+    let header_lines = read_header_lines(
+        &mut BufLines::new(&mut input),
+        &mut Warnings::default(),
+        &Limits::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        header_lines,
+        vec![
+            HeaderLine::FileIdentifierLine,
+            HeaderLine::FormatLine(Format::BinaryLittleEndian {
+                version: "1.0".to_string()
+            }),
+        ]
+    );
+    assert_eq!(input, b"\n\xff");
+}
+
+#[test]
+fn test_read_header_errors() {
+    use super::lines::StrLines;
+
+    let read = |s: &str| read_header(&mut StrLines::new(s.lines().map(|e| e.to_string())));
+    assert_eq!(
+        read("ply\nformat ascii 1.0\nelement vertex -1\nend_header"),
+        Err(PLYError::header_syntax(
+            3,
+            "element vertex -1",
+            "element count must be unsigned integer"
+        ))
+    );
+    assert_eq!(
+        read("ply\nformat ascii 1.0\nproperty float x\nend_header"),
+        Err(PLYError::header_syntax(
+            3,
+            "property float x",
+            "keyword \"property\" cannot be used before element"
+        ))
+    );
+    assert_eq!(
+        read("format ascii 1.0\nend_header"),
+        Err(PLYError::header_syntax(
+            1,
+            "format ascii 1.0",
+            "first line, magic number must be ply"
+        ))
+    );
+    assert_eq!(
+        read("ply\nformat ascii 1.0\nelement vertex 1"),
+        Err(PLYError::header_syntax(
+            4,
+            "",
+            "unexpected end of file, end_header not found"
+        ))
+    );
+}
+
+#[test]
+fn test_read_header_warnings() {
+    use super::{lines::StrLines, ParseMode};
+
+    let input =
+        "ply\nformat ascii 2.0\n\nelement vertex 1\nunknown x\nproperty float x\nend_header";
+    let read = |mode| {
+        let mut warnings = Warnings::new(mode);
+        let mut lines = StrLines::new(input.lines().map(|e| e.to_string()));
+        read_header_with(&mut lines, &mut warnings, &Limits::default())
+            .map(|ply| (ply, warnings.into_vec()))
+    };
+    let (ply, warnings) = read(ParseMode::Lenient).unwrap();
+    assert_eq!(
+        ply,
+        read_header(&mut StrLines::new(input.lines().map(|e| e.to_string()))).unwrap()
+    );
+    assert_eq!(
+        warnings,
+        vec![
+            PLYWarning::EmptyHeaderLine { line: 3 },
+            PLYWarning::UnknownKeyword {
+                line: 5,
+                keyword: "unknown".to_string()
+            },
+            PLYWarning::UnsupportedVersion {
+                version: "2.0".to_string()
+            },
+        ]
+    );
+    assert_eq!(
+        read(ParseMode::Strict),
+        Err(PLYError::header_syntax(3, "", "empty line is not allowed"))
+    );
+}
+
+#[test]
+fn test_read_header_extra_words() {
+    use super::{lines::StrLines, ParseMode};
+
+    let read = |input: &str, mode| {
+        let mut warnings = Warnings::new(mode);
+        let mut lines = StrLines::new(input.lines().map(|e| e.to_string()));
+        read_header_with(&mut lines, &mut warnings, &Limits::default())
+            .map(|ply| (ply, warnings.into_vec()))
+    };
+    let input = "ply\nformat ascii 1.0\nelement vertex 8\nproperty float x\nend_header";
+    for (line, extra, words) in [
+        (2, "format ascii 1.0 extra", "extra"),
+        (3, "element vertex 8 junk", "junk"),
+        (4, "property float x y", "y"),
+        (4, "property float x y z", "y z"),
+    ] {
+        let complete = extra.trim_end_matches(words).trim_end();
+        let with_extra = input.replace(complete, extra);
+        assert_eq!(
+            read(&with_extra, ParseMode::Strict),
+            Err(PLYError::header_syntax(
+                line,
+                words,
+                "extra words after complete line"
+            ))
+        );
+
+        // lenient mode ignores them
+        let (ply, warnings) = read(&with_extra, ParseMode::Lenient).unwrap();
+        assert_eq!(ply, read(input, ParseMode::Strict).unwrap().0);
+        assert_eq!(
+            warnings,
+            vec![PLYWarning::ExtraWords {
+                line,
+                words: words.to_string()
+            }]
+        );
+    }
+}
+
 /// Possible Lines in PLY Header
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub(crate) enum HeaderLine {
@@ -353,12 +738,15 @@ pub(crate) enum HeaderLine {
     FormatLine(Format),
     /// Line like "comment this file is a cube"
     CommentLine(Comment),
+    /// Line like "obj_info num_cols 640"
+    ObjInfoLine(ObjInfo),
     /// Line like "element vertex 8"
     ElementLine { name: String, count: usize },
     /// Line like "property float z"
     PropertyLine {
         name: String,
         prop_type: PLYValueTypeName,
+        naming: TypeNaming,
     },
     /// Line like "property list uchar int vertex_index"
     PropertyListLine(PropertyList),
@@ -376,68 +764,121 @@ impl HeaderLine {
     }
 }
 
-/// Parse PLY Header Line to [HeaderLine]
-pub(crate) fn parse_header_line<S: AsRef<str>>(line: S) -> HeaderLine {
+impl Display for HeaderLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HeaderLine::FileIdentifierLine => write!(f, "{MAGIC_NUMBER}"),
+            HeaderLine::FormatLine(format) => write!(f, "{format}"),
+            HeaderLine::CommentLine(comment) => write!(f, "{comment}"),
+            HeaderLine::ObjInfoLine(obj_info) => write!(f, "{obj_info}"),
+            HeaderLine::ElementLine { name, count } => write!(f, "element {name} {count}"),
+            HeaderLine::PropertyLine {
+                name,
+                prop_type,
+                naming,
+            } => write!(f, "property {} {name}", prop_type.to_str_with(*naming)),
+            HeaderLine::PropertyListLine(prop) => write!(
+                f,
+                "property list {} {} {}",
+                prop.count.to_str_with(prop.count_naming),
+                prop.prop.to_str_with(prop.naming),
+                prop.name
+            ),
+            HeaderLine::EmptyLine => Ok(()),
+            HeaderLine::EndHeader => write!(f, "{END_HEADER}"),
+            HeaderLine::UnknownLine(keyword) => write!(f, "{keyword}"),
+        }
+    }
+}
+
+/// Parse PLY Header Line to [HeaderLine], and words after the complete line if any
+///
+/// Returns reason of failure if line is malformed.
+pub(crate) fn parse_header_line<S: AsRef<str>>(
+    line: S,
+) -> Result<(HeaderLine, Option<String>), &'static str> {
     let mut words = line.as_ref().split_whitespace();
-    match words.next() {
+    let line = match words.next() {
         None => HeaderLine::EmptyLine,
         Some(first_token) => match first_token {
-            "property" => match words.next().expect("property name or \"list\" not found") {
+            "property" => match words.next().ok_or("property type or \"list\" not found")? {
                 "list" => {
-                    let count = PLYValueTypeName::from_str(words.next().unwrap()).unwrap();
-                    let prop = PLYValueTypeName::from_str(words.next().unwrap()).unwrap();
-                    let name = words.next().expect("property name not found").to_string();
-                    HeaderLine::PropertyListLine(PropertyList { count, prop, name })
+                    let (count, count_naming) = parse_type_name(words.next())?;
+                    let (prop, naming) = parse_type_name(words.next())?;
+                    let name = words.next().ok_or("property name not found")?.to_string();
+                    HeaderLine::PropertyListLine(PropertyList {
+                        count,
+                        prop,
+                        name,
+                        count_naming,
+                        naming,
+                    })
+                }
+                prop_type_str => {
+                    let (prop_type, naming) = parse_type_name(Some(prop_type_str))?;
+                    HeaderLine::PropertyLine {
+                        prop_type,
+                        naming,
+                        name: words.next().ok_or("property name not found")?.to_string(),
+                    }
                 }
-                prop_type_str => HeaderLine::PropertyLine {
-                    name: words.next().expect("property name not found").to_string(),
-                    prop_type: PLYValueTypeName::from_str(prop_type_str).unwrap(),
-                },
             },
             "element" => HeaderLine::ElementLine {
-                name: words.next().expect("element name not found").to_string(),
+                name: words.next().ok_or("element name not found")?.to_string(),
                 count: words
                     .next()
-                    .expect("element count not found")
+                    .ok_or("element count not found")?
                     .parse()
-                    .expect("element count must be unsined integer"),
+                    .map_err(|_| "element count must be unsigned integer")?,
             },
             "format" => {
-                HeaderLine::FormatLine(match words.next().expect("format style not found") {
-                    "ascii" => Format::Ascii {
-                        version: words.next().expect("format version not found").to_string(),
-                    },
-                    "binary_little_endian" => Format::BinaryLittleEndian {
-                        version: words.next().expect("format version not found").to_string(),
-                    },
-                    "binary_big_endian" => Format::BinaryBigEndian {
-                        version: words.next().expect("format version not found").to_string(),
-                    },
-                    _ => panic!("unknown format style"),
+                let style = words.next().ok_or("format style not found")?;
+                let version = words.next().ok_or("format version not found")?.to_string();
+                HeaderLine::FormatLine(match style {
+                    "ascii" => Format::Ascii { version },
+                    "binary_little_endian" => Format::BinaryLittleEndian { version },
+                    "binary_big_endian" => Format::BinaryBigEndian { version },
+                    _ => return Err("unknown format style"),
                 })
             }
             "ply" => HeaderLine::FileIdentifierLine,
-            "comment" => HeaderLine::CommentLine(Comment(words.map(|s| s.to_string()).collect())),
+            "comment" => {
+                HeaderLine::CommentLine(Comment(words.by_ref().map(|s| s.to_string()).collect()))
+            }
+            "obj_info" => {
+                HeaderLine::ObjInfoLine(ObjInfo(words.by_ref().map(|s| s.to_string()).collect()))
+            }
             "end_header" => HeaderLine::EndHeader,
             x => {
                 #[cfg(feature = "log")]
                 log::log!(log::Level::Debug, "unknown line identifier: {}", x);
-                HeaderLine::UnknownLine(x.to_string())
+                return Ok((HeaderLine::UnknownLine(x.to_string()), None));
             }
         },
-    }
+    };
+    let extra = words.collect::<Vec<_>>();
+    Ok((line, (!extra.is_empty()).then(|| extra.join(" "))))
+}
+
+/// Parse type name, returns type and its naming
+fn parse_type_name(word: Option<&str>) -> Result<(PLYValueTypeName, TypeNaming), &'static str> {
+    PLYValueTypeName::from_str_with_naming(word.ok_or("property type not found")?)
+        .map_err(|_| "unknown property type")
 }
 
 #[test]
 fn parse_ply_line() {
     let line = "ply";
-    assert_eq!(parse_header_line(line), HeaderLine::FileIdentifierLine);
+    assert_eq!(
+        parse_header_line(line).unwrap().0,
+        HeaderLine::FileIdentifierLine
+    );
 }
 #[test]
 fn parse_ascii_format_line() {
     let line = "format ascii 1.0";
     assert_eq!(
-        parse_header_line(line),
+        parse_header_line(line).unwrap().0,
         HeaderLine::FormatLine(Format::Ascii {
             version: "1.0".to_string()
         })
@@ -447,7 +888,7 @@ fn parse_ascii_format_line() {
 fn parse_comment_line() {
     let line = "comment this file is a cube";
     assert_eq!(
-        parse_header_line(line),
+        parse_header_line(line).unwrap().0,
         HeaderLine::CommentLine(Comment(vec![
             "this".to_string(),
             "file".to_string(),
@@ -458,10 +899,18 @@ fn parse_comment_line() {
     );
 }
 #[test]
+fn parse_obj_info_line() {
+    let line = "obj_info num_cols 640";
+    assert_eq!(
+        parse_header_line(line).unwrap().0,
+        HeaderLine::ObjInfoLine(ObjInfo(vec!["num_cols".to_string(), "640".to_string()]))
+    );
+}
+#[test]
 fn parse_element_line() {
     let line = "element vertex 8";
     assert_eq!(
-        parse_header_line(line),
+        parse_header_line(line).unwrap().0,
         HeaderLine::ElementLine {
             name: "vertex".to_string(),
             count: 8
@@ -472,10 +921,11 @@ fn parse_element_line() {
 fn parse_property_line() {
     let line = "property float x";
     assert_eq!(
-        parse_header_line(line),
+        parse_header_line(line).unwrap().0,
         HeaderLine::PropertyLine {
             name: "x".to_string(),
-            prop_type: PLYValueTypeName::Float
+            prop_type: PLYValueTypeName::Float,
+            naming: TypeNaming::Classic,
         }
     );
 }
@@ -483,16 +933,29 @@ fn parse_property_line() {
 fn parse_property_list_line() {
     let line = "property list uchar int vertex_index";
     assert_eq!(
-        parse_header_line(line),
+        parse_header_line(line).unwrap().0,
         HeaderLine::PropertyListLine(PropertyList {
             name: "vertex_index".to_string(),
             prop: PLYValueTypeName::Int,
-            count: PLYValueTypeName::Uchar
+            count: PLYValueTypeName::Uchar,
+            count_naming: TypeNaming::Classic,
+            naming: TypeNaming::Classic,
         })
     );
 }
 #[test]
 fn parse_empty_line() {
     let line = "";
-    assert_eq!(parse_header_line(line), HeaderLine::EmptyLine);
+    assert_eq!(parse_header_line(line).unwrap().0, HeaderLine::EmptyLine);
+}
+#[test]
+fn parse_unknown_type_line() {
+    assert_eq!(
+        parse_header_line("property float33 x").map(|(line, _)| line),
+        Err("unknown property type")
+    );
+    assert_eq!(
+        parse_header_line("property list uchar").map(|(line, _)| line),
+        Err("property type not found")
+    );
 }
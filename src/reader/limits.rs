@@ -0,0 +1,99 @@
+use crate::{
+    error::{Limit, PLYError, PLYResult},
+    Element, MixedPayload, MixedValue, Payload,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Limits of untrusted input, see [ReadOptions::limits](crate::ReadOptions::limits)
+/// and [PLYReader::with_limits](crate::PLYReader::with_limits)
+///
+/// Reading stops with [PLYError::LimitExceeded] as soon as a limit is exceeded,
+/// so hostile header or list length cannot make reader allocate or decode without bound.
+/// None is no limit.
+pub struct Limits {
+    /// Number of elements declared in header
+    pub max_elements: Option<usize>,
+    /// Number of rows declared for any element
+    pub max_rows: Option<usize>,
+    /// Length of any list of loaded elements, checked before its values are read
+    pub max_list_len: Option<usize>,
+    /// Total size of values decoded into loaded elements, counted in bytes of their types
+    ///
+    /// List lengths are not counted, so it is the size of binary payload without them.
+    pub max_decoded_bytes: Option<usize>,
+    /// Number of header lines, including `ply` and `end_header`
+    pub max_header_lines: Option<usize>,
+    /// Size of header in bytes, including `ply` and `end_header` lines
+    ///
+    /// Header is not read past the first byte over it, so a single long line is not read whole.
+    pub max_header_bytes: Option<usize>,
+}
+
+impl Limits {
+    /// Check declared numbers of elements and rows
+    pub(crate) fn check_header(&self, elements: &[Element]) -> PLYResult<()> {
+        check(Limit::Elements, self.max_elements, elements.len())?;
+        for element in elements {
+            check(Limit::Rows, self.max_rows, element.count())?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns [PLYError::LimitExceeded] if `found` is over `max`
+pub(crate) fn check(limit: Limit, max: Option<usize>, found: usize) -> PLYResult<()> {
+    match max {
+        Some(max) if found > max => Err(PLYError::LimitExceeded { limit, max, found }),
+        _ => Ok(()),
+    }
+}
+
+/// Tracks [Limits] of payload while elements are read, default has no limits
+#[derive(Debug, Default)]
+pub(crate) struct Budget {
+    limits: Limits,
+    decoded: usize,
+}
+
+impl Budget {
+    pub fn new(limits: Limits) -> Self {
+        Self { limits, decoded: 0 }
+    }
+
+    pub fn max_list_len(&self) -> usize {
+        self.limits.max_list_len.unwrap_or(usize::MAX)
+    }
+
+    /// Count values of a row read
+    pub fn decode<P: DecodedBytes>(&mut self, payload: &P) -> PLYResult<()> {
+        self.decoded = self.decoded.saturating_add(payload.decoded_bytes());
+        check(
+            Limit::DecodedBytes,
+            self.limits.max_decoded_bytes,
+            self.decoded,
+        )
+    }
+}
+
+/// Size of decoded values, counted in bytes of their types
+pub(crate) trait DecodedBytes {
+    fn decoded_bytes(&self) -> usize;
+}
+
+impl DecodedBytes for Payload {
+    fn decoded_bytes(&self) -> usize {
+        self.iter().map(|v| v.value_type().bytes_len()).sum()
+    }
+}
+
+impl DecodedBytes for MixedPayload {
+    fn decoded_bytes(&self) -> usize {
+        self.0
+            .iter()
+            .map(|value| match value {
+                MixedValue::Scalar(v) => v.value_type().bytes_len(),
+                MixedValue::List(list) => list.iter().map(|v| v.value_type().bytes_len()).sum(),
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,213 @@
+use std::io::{self, BufRead, Read};
+
+use crate::error::{Limit, PLYError, PLYResult};
+
+/// Source of text lines (header and ascii payload)
+pub(crate) trait AsciiLines {
+    /// Returns next line without line terminator, or None at the end of input
+    fn next_line(&mut self) -> PLYResult<Option<&str>>;
+
+    /// Returns byte offset of the line last returned by [AsciiLines::next_line]
+    fn line_offset(&self) -> usize;
+
+    /// Returns false if the last line returned ended without line terminator,
+    /// which happens only at the end of input
+    fn is_line_terminated(&self) -> bool {
+        true
+    }
+
+    /// Limit bytes read from the start of input by following lines to `max`, None to lift it
+    ///
+    /// Lines over it are [PLYError::LimitExceeded] of [Limit::HeaderBytes].
+    /// Lines which are already in memory are not limited.
+    fn limit_header_bytes(&mut self, _max: Option<usize>) {}
+}
+
+/// [AsciiLines] read from [BufRead]
+///
+/// Reads exactly one line at a time, so reader is not advanced over the current line.
+pub(crate) struct BufLines<R> {
+    reader: R,
+    line: Vec<u8>,
+    line_offset: usize,
+    position: usize,
+    terminated: bool,
+    max_header_bytes: Option<usize>,
+}
+
+impl<R: BufRead> BufLines<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: Vec::new(),
+            line_offset: 0,
+            position: 0,
+            terminated: true,
+            max_header_bytes: None,
+        }
+    }
+
+    /// Returns byte offset of the next line
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> AsciiLines for BufLines<R> {
+    fn next_line(&mut self) -> PLYResult<Option<&str>> {
+        self.line.clear();
+        self.line_offset = self.position;
+        let len = match self.max_header_bytes {
+            None => self.reader.read_until(b'\n', &mut self.line)?,
+            Some(max) => {
+                // a byte over `max` is read to tell line ending at `max` from longer one
+                let room = max.saturating_sub(self.position).saturating_add(1);
+                let len = (&mut self.reader)
+                    .take(room as u64)
+                    .read_until(b'\n', &mut self.line)?;
+                if self.position + len > max {
+                    return Err(PLYError::LimitExceeded {
+                        limit: Limit::HeaderBytes,
+                        max,
+                        found: self.position + len,
+                    });
+                }
+                len
+            }
+        };
+        if len == 0 {
+            return Ok(None);
+        }
+        let line = std::str::from_utf8(&self.line)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.position += len;
+        self.terminated = line.ends_with('\n');
+        Ok(Some(line.trim_end_matches(['\n', '\r'])))
+    }
+
+    fn line_offset(&self) -> usize {
+        self.line_offset
+    }
+
+    fn is_line_terminated(&self) -> bool {
+        self.terminated
+    }
+
+    fn limit_header_bytes(&mut self, max: Option<usize>) {
+        self.max_header_bytes = max;
+    }
+}
+
+/// [AsciiLines] over iterator of lines
+///
+/// Each line is assumed to be terminated by `\n` when counting byte offset.
+pub(crate) struct StrLines<I> {
+    lines: I,
+    line: String,
+    line_offset: usize,
+    position: usize,
+}
+
+impl<I: Iterator<Item = String>> StrLines<I> {
+    pub fn new(lines: I) -> Self {
+        Self {
+            lines,
+            line: String::new(),
+            line_offset: 0,
+            position: 0,
+        }
+    }
+}
+
+impl<I: Iterator<Item = String>> AsciiLines for StrLines<I> {
+    fn next_line(&mut self) -> PLYResult<Option<&str>> {
+        self.line_offset = self.position;
+        match self.lines.next() {
+            Some(line) => {
+                self.line = line;
+                self.position += self.line.len() + 1;
+                Ok(Some(self.line.as_str()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn line_offset(&self) -> usize {
+        self.line_offset
+    }
+}
+
+/// [AsciiLines] over text read elsewhere, like lines buffered by async reader
+///
+/// `offset` is byte offset of the start of `text`.
+#[cfg(feature = "async")]
+pub(crate) struct TextLines<'a> {
+    text: &'a str,
+    line_offset: usize,
+    position: usize,
+    terminated: bool,
+}
+
+#[cfg(feature = "async")]
+impl<'a> TextLines<'a> {
+    pub fn new(text: &'a str, offset: usize) -> Self {
+        Self {
+            text,
+            line_offset: offset,
+            position: offset,
+            terminated: true,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a> AsciiLines for TextLines<'a> {
+    fn next_line(&mut self) -> PLYResult<Option<&str>> {
+        self.line_offset = self.position;
+        if self.text.is_empty() {
+            return Ok(None);
+        }
+        let (line, len) = match self.text.find('\n') {
+            Some(i) => (&self.text[..i], i + 1),
+            None => (self.text, self.text.len()),
+        };
+        self.terminated = len > line.len();
+        self.position += len;
+        self.text = &self.text[len..];
+        Ok(Some(line.trim_end_matches('\r')))
+    }
+
+    fn line_offset(&self) -> usize {
+        self.line_offset
+    }
+
+    fn is_line_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+#[test]
+fn test_buf_lines() {
+    let mut input: &[u8] = b"ply\r\nend_header\n\xff";
+    let mut lines = BufLines::new(&mut input);
+    assert_eq!(lines.next_line().unwrap(), Some("ply"));
+    assert_eq!(lines.next_line().unwrap(), Some("end_header"));
+    assert!(lines.is_line_terminated());
+    assert_eq!(lines.line_offset(), 5);
+    assert_eq!(lines.position(), 16);
+    assert_eq!(*lines.into_inner(), &b"\xff"[..]);
+}
+
+#[test]
+fn test_buf_lines_terminated() {
+    let mut input: &[u8] = b"0 1\n2 3";
+    let mut lines = BufLines::new(&mut input);
+    assert_eq!(lines.next_line().unwrap(), Some("0 1"));
+    assert!(lines.is_line_terminated());
+    assert_eq!(lines.next_line().unwrap(), Some("2 3"));
+    assert!(!lines.is_line_terminated());
+}
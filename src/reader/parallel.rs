@@ -0,0 +1,279 @@
+//! Parallel payload decoding (feature `rayon`)
+
+use rayon::prelude::*;
+
+use std::ops::Range;
+
+use crate::{error::PLYResult, GenericElement, Payload, Property};
+
+use super::{
+    limits::Budget,
+    lines::AsciiLines,
+    payload::{read_elemet_payload_ascii, too_few_values, ReadPayload, RowError},
+    tokens::{Tokens, Words},
+    Bytes,
+};
+
+/// Number of rows read before parsing them in parallel
+const CHUNK_ROWS: usize = 1 << 16;
+
+/// Read ascii payload of scalar only element, parsing chunks of rows in parallel.
+///
+/// Every row has the same number of words, so words of a chunk are split into rows without parsing.
+/// Rows and errors are the same as [read_elemet_payload_ascii](super::payload::read_elemet_payload_ascii).
+pub(crate) fn read_element_payload_ascii_par<L: AsciiLines>(
+    name: &str,
+    element: &mut GenericElement<Property>,
+    tokens: &mut Tokens<L>,
+    budget: &mut Budget,
+) -> PLYResult<()> {
+    let stride = element.props.props.len();
+    if stride == 0 {
+        return read_elemet_payload_ascii(name, element, tokens, budget);
+    }
+
+    // words of chunk are copied into `text`, `words` are their ranges
+    let mut text = String::new();
+    let mut words: Vec<Range<usize>> = Vec::new();
+    let mut offsets = Vec::new();
+    let mut row = 0;
+    while row < element.count {
+        text.clear();
+        words.clear();
+        offsets.clear();
+        let rows = CHUNK_ROWS.min(element.count - row);
+        while offsets.len() < rows && tokens.skip_whitespace()? {
+            offsets.push(tokens.offset());
+            for _ in 0..stride {
+                match tokens.next_word() {
+                    Some(word) => {
+                        text.push_str(word);
+                        words.push(text.len() - word.len()..text.len());
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        let props = &element.props;
+        let payloads: Vec<Result<Payload, RowError>> = words
+            .par_chunks(stride)
+            .map(|row| {
+                props.read_as_ascii(
+                    &mut RowWords {
+                        text: &text,
+                        words: row.iter(),
+                    },
+                    usize::MAX,
+                )
+            })
+            .collect();
+        for (payload, offset) in payloads.into_iter().zip(offsets.iter()) {
+            let payload = payload.map_err(|e| match e {
+                // the last row of input ended in the middle
+                e if e == too_few_values() => tokens.row_error(RowError::Eof, name, row, *offset),
+                e => e.at(name, row, *offset),
+            })?;
+            budget.decode(&payload)?;
+            element.payloads.push(payload);
+            row += 1;
+        }
+
+        if offsets.len() < rows {
+            return Err(tokens.row_error(RowError::Eof, name, row, tokens.offset()));
+        }
+    }
+    Ok(())
+}
+
+/// Words of a row in chunk, by their ranges in text of chunk
+struct RowWords<'a> {
+    text: &'a str,
+    words: std::slice::Iter<'a, Range<usize>>,
+}
+
+impl<'a> Words for RowWords<'a> {
+    fn next_word(&mut self) -> Option<&str> {
+        self.words.next().map(|word| &self.text[word.clone()])
+    }
+}
+
+/// Read binary payload of scalar only element, decoding chunks of rows in parallel.
+///
+/// Every row has the same size, so bytes of a chunk are split into rows without decoding.
+/// Rows and errors are the same as
+/// [read_element_payload_le_bytes](super::payload::read_element_payload_le_bytes) (`_be_bytes`).
+pub(crate) fn read_element_payload_bytes_par<I: Iterator<Item = u8>>(
+    name: &str,
+    element: &mut GenericElement<Property>,
+    bytes: &mut Bytes<I>,
+    big_endian: bool,
+    budget: &mut Budget,
+) -> PLYResult<()> {
+    let stride: usize = element.props.props.iter().map(|t| t.bytes_len()).sum();
+    let props = &element.props;
+    let decode = |row: &[u8]| {
+        let mut row = row.iter().copied();
+        if big_endian {
+            props.read_as_be(&mut row, usize::MAX)
+        } else {
+            props.read_as_le(&mut row, usize::MAX)
+        }
+    };
+    if stride == 0 {
+        for _ in 0..element.count {
+            let payload = decode(&[]).map_err(|e| e.at(name, 0, bytes.offset()))?;
+            budget.decode(&payload)?;
+            element.payloads.push(payload);
+        }
+        return Ok(());
+    }
+
+    let mut chunk = Vec::new();
+    let mut row = 0;
+    while row < element.count {
+        let rows = CHUNK_ROWS.min(element.count - row);
+        chunk.clear();
+        chunk.extend(bytes.by_ref().take(rows * stride));
+
+        let payloads = chunk
+            .par_chunks_exact(stride)
+            .map(decode)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.at(name, row, bytes.offset()))?;
+        row += payloads.len();
+        for payload in &payloads {
+            budget.decode(payload)?;
+        }
+        element.payloads.extend(payloads);
+
+        if chunk.len() < rows * stride {
+            return Err(RowError::Eof.at(name, row, bytes.offset()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read_element_payload_ascii_par, read_element_payload_bytes_par};
+    use crate::{
+        reader::{
+            limits::Budget,
+            lines::StrLines,
+            payload::{read_element_payload_le_bytes, read_elemet_payload_ascii, Bytes},
+            tokens::Tokens,
+        },
+        GenericElement, PLYValueTypeName, Property,
+    };
+
+    fn element() -> GenericElement<Property> {
+        let mut props = Property::new();
+        props.push_prop("x", PLYValueTypeName::Float);
+        props.push_prop("i", PLYValueTypeName::Int);
+        let mut element = GenericElement::new(props);
+        element.count = 3 * super::CHUNK_ROWS / 2;
+        element
+    }
+
+    fn lines(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("{}.5 {}", i, i)).collect()
+    }
+
+    #[test]
+    fn test_par_ascii_same_as_sequential() {
+        let mut sequential = element();
+        let mut input = lines(sequential.count);
+        // rows wrapped across lines, and several rows on a line
+        input[3] = "3.5 3 4.5".to_string();
+        input[4] = "4".to_string();
+        read_elemet_payload_ascii(
+            "vertex",
+            &mut sequential,
+            &mut Tokens::new(StrLines::new(input.iter().cloned())),
+            &mut Budget::default(),
+        )
+        .unwrap();
+        let mut parallel = element();
+        read_element_payload_ascii_par(
+            "vertex",
+            &mut parallel,
+            &mut Tokens::new(StrLines::new(input.iter().cloned())),
+            &mut Budget::default(),
+        )
+        .unwrap();
+        assert_eq!(sequential, parallel);
+    }
+
+    #[test]
+    fn test_par_ascii_errors() {
+        let count = element().count;
+        let mut invalid = lines(count);
+        invalid[super::CHUNK_ROWS + 1] = "x 0".to_string();
+        invalid[super::CHUNK_ROWS + 5] = "0".to_string();
+        let truncated = lines(count - 1);
+        let mut invalid_last = lines(count);
+        invalid_last[count - 1] = "0.5 x".to_string();
+        let mut partial = lines(count);
+        partial[count - 1] = "0.5".to_string();
+        // rows are not aligned to lines after missing value
+        let mut shifted = lines(count - 1);
+        shifted[10] = "0.5".to_string();
+
+        for input in [invalid, truncated, invalid_last, partial, shifted] {
+            let sequential = read_elemet_payload_ascii(
+                "vertex",
+                &mut element(),
+                &mut Tokens::new(StrLines::new(input.iter().cloned())),
+                &mut Budget::default(),
+            );
+            let parallel = read_element_payload_ascii_par(
+                "vertex",
+                &mut element(),
+                &mut Tokens::new(StrLines::new(input.iter().cloned())),
+                &mut Budget::default(),
+            );
+            assert!(sequential.is_err());
+            assert_eq!(sequential, parallel);
+        }
+    }
+
+    #[test]
+    fn test_par_binary_same_as_sequential() {
+        let count = element().count;
+        let mut input = Vec::new();
+        for i in 0..count {
+            input.extend_from_slice(&(i as f32 + 0.5).to_le_bytes());
+            input.extend_from_slice(&(-(i as i32)).to_le_bytes());
+        }
+        let read = |input: &[u8], parallel: bool| {
+            let mut element = element();
+            let mut bytes = Bytes::new(input.iter().copied(), 100);
+            let result = if parallel {
+                read_element_payload_bytes_par(
+                    "vertex",
+                    &mut element,
+                    &mut bytes,
+                    false,
+                    &mut Budget::default(),
+                )
+            } else {
+                read_element_payload_le_bytes(
+                    "vertex",
+                    &mut element,
+                    &mut bytes,
+                    &mut Budget::default(),
+                )
+            };
+            result.map(|_| element)
+        };
+
+        let sequential = read(&input, false).unwrap();
+        assert_eq!(sequential.payloads.len(), count);
+        assert_eq!(Ok(sequential), read(&input, true));
+
+        let truncated = &input[..input.len() - 3];
+        assert!(read(truncated, false).is_err());
+        assert_eq!(read(truncated, false), read(truncated, true));
+    }
+}
@@ -1,42 +1,482 @@
-use std::convert::TryInto;
+use std::{
+    convert::TryInto,
+    io::{self, BufRead},
+    ops::Range,
+};
 
-use crate::{GenericElement, Payload, Property, PropertyList};
+use crate::{
+    error::{Limit, PLYError, PLYResult},
+    GenericElement, MixedPayload, MixedProperty, MixedValue, PLYValue, PLYValueTypeName, Payload,
+    Property, PropertyKind, PropertyList,
+};
 
-pub(crate) fn read_elemet_payload_ascii<P, I>(element: &mut GenericElement<P>, lines: &mut I)
+use super::{
+    limits::{Budget, DecodedBytes},
+    lines::AsciiLines,
+    tokens::{Tokens, Words},
+};
+
+pub(crate) fn read_elemet_payload_ascii<P, L>(
+    name: &str,
+    element: &mut GenericElement<P, P::Payload>,
+    tokens: &mut Tokens<L>,
+    budget: &mut Budget,
+) -> PLYResult<()>
 where
-    P: ReadPayload<Payload = Payload>,
-    I: Iterator<Item = String>,
+    P: ReadPayload,
+    P::Payload: DecodedBytes,
+    L: AsciiLines,
 {
-    for _ in 0..element.count {
-        let line = lines.next().unwrap();
-        let payload = element.props.read_as_ascii(line);
+    for row in 0..element.count {
+        let payload = read_row_ascii(name, &element.props, row, tokens, budget.max_list_len())?;
+        budget.decode(&payload)?;
         element.payloads.push(payload);
     }
+    Ok(())
 }
-pub(crate) fn read_element_payload_be_bytes<P, I>(element: &mut GenericElement<P>, bytes: &mut I)
+pub(crate) fn read_element_payload_be_bytes<P, I>(
+    name: &str,
+    element: &mut GenericElement<P, P::Payload>,
+    bytes: &mut Bytes<I>,
+    budget: &mut Budget,
+) -> PLYResult<()>
 where
-    P: ReadPayload<Payload = Payload>,
+    P: ReadPayload,
+    P::Payload: DecodedBytes,
     I: Iterator<Item = u8>,
 {
-    for _ in 0..element.count {
-        let payload = element.props.read_as_be(bytes);
+    for row in 0..element.count {
+        let payload = read_row_be(name, &element.props, row, bytes, budget.max_list_len())?;
+        budget.decode(&payload)?;
         element.payloads.push(payload);
     }
+    Ok(())
 }
 
-pub(crate) fn read_element_payload_le_bytes<P, I>(element: &mut GenericElement<P>, bytes: &mut I)
+pub(crate) fn read_element_payload_le_bytes<P, I>(
+    name: &str,
+    element: &mut GenericElement<P, P::Payload>,
+    bytes: &mut Bytes<I>,
+    budget: &mut Budget,
+) -> PLYResult<()>
 where
-    P: ReadPayload<Payload = Payload>,
+    P: ReadPayload,
+    P::Payload: DecodedBytes,
     I: Iterator<Item = u8>,
 {
-    for _ in 0..element.count {
-        let payload = element.props.read_as_le(bytes);
+    for row in 0..element.count {
+        let payload = read_row_le(name, &element.props, row, bytes, budget.max_list_len())?;
+        budget.decode(&payload)?;
         element.payloads.push(payload);
     }
+    Ok(())
+}
+
+/// Read `row` of element `name` from the next words, regardless of line breaks
+///
+/// Error offset is the offset of the first word of the row, or the end of input if there is none.
+/// List longer than `max_list_len` is [PLYError::LimitExceeded].
+pub(crate) fn read_row_ascii<P, L>(
+    name: &str,
+    props: &P,
+    row: usize,
+    tokens: &mut Tokens<L>,
+    max_list_len: usize,
+) -> PLYResult<P::Payload>
+where
+    P: ReadPayload,
+    L: AsciiLines,
+{
+    tokens.skip_whitespace()?;
+    let offset = tokens.offset();
+    props
+        .read_as_ascii(tokens, max_list_len)
+        .map_err(|e| tokens.row_error(e, name, row, offset))
+}
+
+pub(crate) fn read_row_be<P, I>(
+    name: &str,
+    props: &P,
+    row: usize,
+    bytes: &mut Bytes<I>,
+    max_list_len: usize,
+) -> PLYResult<P::Payload>
+where
+    P: ReadPayload,
+    I: Iterator<Item = u8>,
+{
+    props
+        .read_as_be(bytes, max_list_len)
+        .map_err(|e| e.at(name, row, bytes.offset()))
+}
+
+pub(crate) fn read_row_le<P, I>(
+    name: &str,
+    props: &P,
+    row: usize,
+    bytes: &mut Bytes<I>,
+    max_list_len: usize,
+) -> PLYResult<P::Payload>
+where
+    P: ReadPayload,
+    I: Iterator<Item = u8>,
+{
+    props
+        .read_as_le(bytes, max_list_len)
+        .map_err(|e| e.at(name, row, bytes.offset()))
+}
+
+/// Skip `rows` of element `name` without parsing values, but list lengths
+pub(crate) fn skip_rows_ascii<L: AsciiLines>(
+    name: &str,
+    props: &MixedProperty,
+    rows: Range<usize>,
+    tokens: &mut Tokens<L>,
+) -> PLYResult<()> {
+    for row in rows {
+        tokens.skip_whitespace()?;
+        let offset = tokens.offset();
+        skip_row_ascii(props, tokens).map_err(|e| tokens.row_error(e, name, row, offset))?;
+    }
+    Ok(())
+}
+
+fn skip_row_ascii<W: Words>(props: &MixedProperty, words: &mut W) -> Result<(), RowError> {
+    for (_, kind) in props.iter() {
+        let len = match kind {
+            PropertyKind::Scalar(_) => 1,
+            PropertyKind::List { .. } => parse_list_len(words.next_word(), usize::MAX)?,
+        };
+        if !words.skip_words(len) {
+            return Err(too_few_values());
+        }
+    }
+    Ok(())
+}
+
+/// Skip `rows` of element `name` without constructing payload
+///
+/// Rows of fixed size are skipped at once by [SkipBytes], and the row where input ended is worked out
+/// from the bytes skipped. Rows with list are walked by their lengths.
+pub(crate) fn skip_rows_bytes<I: SkipBytes>(
+    name: &str,
+    props: &MixedProperty,
+    rows: Range<usize>,
+    bytes: &mut Bytes<I>,
+    big_endian: bool,
+) -> PLYResult<()> {
+    let fixed_stride = props.iter().try_fold(0, |stride, (_, kind)| match kind {
+        PropertyKind::Scalar(ty) => Some(stride + ty.bytes_len()),
+        PropertyKind::List { .. } => None,
+    });
+    match fixed_stride {
+        Some(0) => Ok(()),
+        Some(stride) => {
+            let start = bytes.offset();
+            let len = rows.len().saturating_mul(stride);
+            let skipped = bytes.skip_bytes(len);
+            if skipped < len {
+                let complete = skipped / stride;
+                return Err(RowError::Eof.at(
+                    name,
+                    rows.start + complete,
+                    start + complete * stride,
+                ));
+            }
+            Ok(())
+        }
+        None => {
+            for row in rows {
+                let offset = bytes.offset();
+                let skipped = skip_row_bytes(props, bytes, big_endian)
+                    .map_err(|e| e.at(name, row, offset))?;
+                if !skipped {
+                    return Err(RowError::Eof.at(name, row, offset));
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn skip_row_bytes<I: Iterator<Item = u8>>(
+    props: &MixedProperty,
+    bytes: &mut Bytes<I>,
+    big_endian: bool,
+) -> Result<bool, RowError> {
+    for (_, kind) in props.iter() {
+        let len = match kind {
+            PropertyKind::Scalar(ty) => ty.bytes_len(),
+            PropertyKind::List { count, prop } => {
+                let len = if big_endian {
+                    count.from_be_bytes(bytes)
+                } else {
+                    count.from_le_bytes(bytes)
+                };
+                match len {
+                    Some(len) => list_len(len, usize::MAX)?.saturating_mul(prop.bytes_len()),
+                    None => return Ok(false),
+                }
+            }
+        };
+        if !bytes.skip(len) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Byte iterator over binary payload, counts bytes consumed
+pub(crate) struct Bytes<I> {
+    bytes: I,
+    offset: usize,
+}
+
+impl<I> Bytes<I> {
+    /// `offset` is byte offset of the first byte from start of file
+    pub fn new(bytes: I, offset: usize) -> Self {
+        Self { bytes, offset }
+    }
+
+    /// Returns byte offset of the next byte
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.bytes
+    }
+
+    /// Skip `n` bytes, returns false if input ended before
+    pub fn skip(&mut self, n: usize) -> bool
+    where
+        I: Iterator<Item = u8>,
+    {
+        n == 0 || self.nth(n - 1).is_some()
+    }
+
+    /// Skip up to `n` bytes, returns number of bytes skipped
+    pub fn skip_bytes(&mut self, n: usize) -> usize
+    where
+        I: SkipBytes,
+    {
+        let skipped = self.bytes.skip_bytes(n);
+        self.offset += skipped;
+        skipped
+    }
+}
+
+/// Byte input which skips many bytes at once
+pub(crate) trait SkipBytes: Iterator<Item = u8> {
+    /// Skip up to `n` bytes, returns number of bytes skipped, less than `n` if input ended
+    fn skip_bytes(&mut self, n: usize) -> usize;
+}
+
+/// In-memory input, skipped with `nth`
+impl<I: ExactSizeIterator<Item = u8>> SkipBytes for I {
+    fn skip_bytes(&mut self, n: usize) -> usize {
+        let n = n.min(self.len());
+        if n > 0 {
+            self.nth(n - 1);
+        }
+        n
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for Bytes<I> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.bytes.next()?;
+        self.offset += 1;
+        Some(byte)
+    }
+
+    /// Skips with `nth` of inner iterator, offset is not advanced if input ended
+    fn nth(&mut self, n: usize) -> Option<u8> {
+        let byte = self.bytes.nth(n)?;
+        self.offset += n + 1;
+        Some(byte)
+    }
+}
+
+/// Bytes of [BufRead] as iterator
+///
+/// Iteration stops at the first I/O error, which is kept to be reported instead of EOF.
+pub(crate) struct ReadBytes<R> {
+    reader: R,
+    error: Option<io::Error>,
+}
+
+impl<R: BufRead> ReadBytes<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            error: None,
+        }
+    }
+
+    /// Takes I/O error which stopped iteration
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+}
+
+impl<R: BufRead> Iterator for ReadBytes<R> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => {
+                    let byte = *buf.first()?;
+                    self.reader.consume(1);
+                    return Some(byte);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.error = Some(e);
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Consumes buffered bytes at once instead of byte by byte
+    fn nth(&mut self, mut n: usize) -> Option<u8> {
+        loop {
+            let (len, byte) = match self.reader.fill_buf() {
+                Ok(buf) => (buf.len(), buf.get(n).copied()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.error = Some(e);
+                    return None;
+                }
+            };
+            if len == 0 {
+                return None;
+            }
+            if let Some(byte) = byte {
+                self.reader.consume(n + 1);
+                return Some(byte);
+            }
+            self.reader.consume(len);
+            n -= len;
+        }
+    }
+}
+
+/// Consumes buffered bytes of [BufRead] at once
+impl<R: BufRead> SkipBytes for ReadBytes<R> {
+    fn skip_bytes(&mut self, n: usize) -> usize {
+        let mut skipped = 0;
+        while skipped < n {
+            let len = match self.reader.fill_buf() {
+                Ok(buf) => buf.len().min(n - skipped),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.error = Some(e);
+                    break;
+                }
+            };
+            if len == 0 {
+                break;
+            }
+            self.reader.consume(len);
+            skipped += len;
+        }
+        skipped
+    }
+}
+
+/// Report I/O error which ended the byte stream rather than EOF
+pub(crate) fn io_error_or<R: BufRead>(bytes: &mut Bytes<ReadBytes<R>>, e: PLYError) -> PLYError {
+    match bytes.get_mut().take_error() {
+        Some(io) => PLYError::Io(io),
+        None => e,
+    }
+}
+
+/// Error while reading a row.
+///
+/// Position of the row is not known here, [RowError::at] converts it into [PLYError].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RowError {
+    /// input ended in the middle of row
+    Eof,
+    /// row is malformed
+    Invalid(String),
+    /// list is longer than [Limits::max_list_len](crate::Limits::max_list_len)
+    ListTooLong { len: usize, max: usize },
+}
+
+impl RowError {
+    pub(super) fn at(self, element: &str, row: usize, offset: usize) -> PLYError {
+        match self {
+            RowError::Eof => PLYError::UnexpectedEof {
+                element: element.to_string(),
+                row,
+                offset,
+            },
+            RowError::Invalid(reason) => PLYError::Payload {
+                element: element.to_string(),
+                row,
+                offset,
+                reason,
+            },
+            RowError::ListTooLong { len, max } => PLYError::LimitExceeded {
+                limit: Limit::ListLength,
+                max,
+                found: len,
+            },
+        }
+    }
+}
+
+fn parse_ascii(prop: PLYValueTypeName, word: &str) -> Result<PLYValue, RowError> {
+    prop.parse(word)
+        .map_err(|_| RowError::Invalid(format!("cannot parse \"{word}\" as {}", prop.to_str())))
+}
+
+pub(super) fn too_few_values() -> RowError {
+    RowError::Invalid("too few values in row".to_string())
+}
+
+pub(super) fn next_ascii<W: Words>(
+    words: &mut W,
+    prop: PLYValueTypeName,
+) -> Result<PLYValue, RowError> {
+    match words.next_word() {
+        Some(s) => parse_ascii(prop, s),
+        None => Err(too_few_values()),
+    }
+}
+
+pub(super) fn parse_list_len(word: Option<&str>, max: usize) -> Result<usize, RowError> {
+    let word = word.ok_or_else(|| RowError::Invalid("list length not found".to_string()))?;
+    let len = word
+        .parse()
+        .map_err(|_| RowError::Invalid(format!("invalid list length \"{word}\"")))?;
+    check_list_len(len, max)
+}
+
+pub(super) fn list_len(count: PLYValue, max: usize) -> Result<usize, RowError> {
+    let len = count
+        .try_into()
+        .map_err(|_| RowError::Invalid(format!("invalid list length {count}")))?;
+    check_list_len(len, max)
+}
+
+fn check_list_len(len: usize, max: usize) -> Result<usize, RowError> {
+    if len > max {
+        return Err(RowError::ListTooLong { len, max });
+    }
+    Ok(len)
 }
 
 #[test]
 fn test_read_element_payload_ascii() {
+    use super::lines::StrLines;
     use crate::*;
     let mut element = GenericElement {
         count: 8,
@@ -47,6 +487,7 @@ fn test_read_element_payload_ascii() {
                 PLYValueTypeName::Uchar,
             ],
             names: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+            namings: vec![TypeNaming::Classic; 3],
         },
         payloads: Vec::<Payload>::with_capacity(8),
     };
@@ -59,7 +500,13 @@ fn test_read_element_payload_ascii() {
 1 0 1
 1 1 1
 1 1 0";
-    read_elemet_payload_ascii(&mut element, &mut lines.lines().map(|e| e.to_string()));
+    read_elemet_payload_ascii(
+        "color",
+        &mut element,
+        &mut Tokens::new(StrLines::new(lines.lines().map(|e| e.to_string()))),
+        &mut Budget::default(),
+    )
+    .unwrap();
     assert_eq!(
         element.payloads,
         vec![
@@ -119,6 +566,7 @@ fn test_read_element_payload_be() {
                 PLYValueTypeName::Uchar,
             ],
             names: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+            namings: vec![TypeNaming::Classic; 3],
         },
         payloads: Vec::with_capacity(8),
     };
@@ -156,7 +604,13 @@ fn test_read_element_payload_be() {
         1u8.to_be(),
         0u8.to_be(),
     ];
-    read_element_payload_be_bytes(&mut element, &mut bytes.iter().copied());
+    read_element_payload_be_bytes(
+        "color",
+        &mut element,
+        &mut Bytes::new(bytes.iter().copied(), 0),
+        &mut Budget::default(),
+    )
+    .unwrap();
     assert_eq!(
         element.payloads,
         vec![
@@ -216,6 +670,7 @@ fn test_read_element_payload_le() {
                 PLYValueTypeName::Uchar,
             ],
             names: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+            namings: vec![TypeNaming::Classic; 3],
         },
         payloads: Vec::with_capacity(8),
     };
@@ -253,7 +708,13 @@ fn test_read_element_payload_le() {
         1u8.to_le(),
         0u8.to_le(),
     ];
-    read_element_payload_le_bytes(&mut element, &mut bytes.iter().copied());
+    read_element_payload_le_bytes(
+        "color",
+        &mut element,
+        &mut Bytes::new(bytes.iter().copied(), 0),
+        &mut Budget::default(),
+    )
+    .unwrap();
     assert_eq!(
         element.payloads,
         vec![
@@ -304,60 +765,186 @@ fn test_read_element_payload_le() {
 pub(crate) trait ReadPayload {
     type Payload;
 
-    fn read_as_ascii<S: AsRef<str>>(&self, line: S) -> Self::Payload;
-    fn read_as_be<I: Iterator<Item = u8>>(&self, bytes: &mut I) -> Self::Payload;
-    fn read_as_le<I: Iterator<Item = u8>>(&self, bytes: &mut I) -> Self::Payload;
+    /// Read values of a row from ascii words, words after the row are not consumed
+    ///
+    /// Lists longer than `max_list_len` are rejected before their values are read.
+    fn read_as_ascii<W: Words>(
+        &self,
+        words: &mut W,
+        max_list_len: usize,
+    ) -> Result<Self::Payload, RowError>;
+    fn read_as_be<I: Iterator<Item = u8>>(
+        &self,
+        bytes: &mut I,
+        max_list_len: usize,
+    ) -> Result<Self::Payload, RowError>;
+    fn read_as_le<I: Iterator<Item = u8>>(
+        &self,
+        bytes: &mut I,
+        max_list_len: usize,
+    ) -> Result<Self::Payload, RowError>;
 }
 
 impl ReadPayload for Property {
     type Payload = Payload;
 
-    fn read_as_ascii<S: AsRef<str>>(&self, line: S) -> Payload {
-        let words = line.as_ref().split_ascii_whitespace();
-        Payload(
-            self.props
-                .iter()
-                .zip(words)
-                .map(|(t, s)| t.parse(s).unwrap())
-                .collect(),
-        )
+    fn read_as_ascii<W: Words>(&self, words: &mut W, _: usize) -> Result<Payload, RowError> {
+        self.props
+            .iter()
+            .map(|t| next_ascii(words, *t))
+            .collect::<Result<_, _>>()
+            .map(Payload)
     }
 
-    fn read_as_be<I: Iterator<Item = u8>>(&self, bytes: &mut I) -> Payload {
-        Payload(self.props.iter().map(|t| t.from_be_bytes(bytes)).collect())
+    fn read_as_be<I: Iterator<Item = u8>>(
+        &self,
+        bytes: &mut I,
+        _: usize,
+    ) -> Result<Payload, RowError> {
+        self.props
+            .iter()
+            .map(|t| t.from_be_bytes(bytes).ok_or(RowError::Eof))
+            .collect::<Result<_, _>>()
+            .map(Payload)
     }
 
-    fn read_as_le<I: Iterator<Item = u8>>(&self, bytes: &mut I) -> Payload {
-        Payload(self.props.iter().map(|t| t.from_le_bytes(bytes)).collect())
+    fn read_as_le<I: Iterator<Item = u8>>(
+        &self,
+        bytes: &mut I,
+        _: usize,
+    ) -> Result<Payload, RowError> {
+        self.props
+            .iter()
+            .map(|t| t.from_le_bytes(bytes).ok_or(RowError::Eof))
+            .collect::<Result<_, _>>()
+            .map(Payload)
     }
 }
 
 impl ReadPayload for PropertyList {
     type Payload = Payload;
 
-    fn read_as_ascii<S: AsRef<str>>(&self, line: S) -> Payload {
-        let mut words = line.as_ref().split_ascii_whitespace();
-        let count_usize = words.next().unwrap().parse().unwrap();
-        let data = words
-            .map(|s| self.prop.parse(s).unwrap())
-            .collect::<Vec<_>>();
-        assert_eq!(data.len(), count_usize);
+    fn read_as_ascii<W: Words>(
+        &self,
+        words: &mut W,
+        max_list_len: usize,
+    ) -> Result<Payload, RowError> {
+        let count = parse_list_len(words.next_word(), max_list_len)?;
+        let data = (0..count)
+            .map(|_| next_ascii(words, self.prop))
+            .collect::<Result<_, _>>()?;
+        Ok(Payload(data))
+    }
+
+    fn read_as_be<I: Iterator<Item = u8>>(
+        &self,
+        bytes: &mut I,
+        max_list_len: usize,
+    ) -> Result<Payload, RowError> {
+        let count = list_len(
+            self.count.from_be_bytes(bytes).ok_or(RowError::Eof)?,
+            max_list_len,
+        )?;
+        let data = (0..count)
+            .map(|_| self.prop.from_be_bytes(bytes).ok_or(RowError::Eof))
+            .collect::<Result<_, _>>()?;
+        Ok(Payload(data))
+    }
+
+    fn read_as_le<I: Iterator<Item = u8>>(
+        &self,
+        bytes: &mut I,
+        max_list_len: usize,
+    ) -> Result<Payload, RowError> {
+        let count = list_len(
+            self.count.from_le_bytes(bytes).ok_or(RowError::Eof)?,
+            max_list_len,
+        )?;
+        let data = (0..count)
+            .map(|_| self.prop.from_le_bytes(bytes).ok_or(RowError::Eof))
+            .collect::<Result<_, _>>()?;
+        Ok(Payload(data))
+    }
+}
+impl ReadPayload for MixedProperty {
+    type Payload = MixedPayload;
 
-        Payload(data)
+    fn read_as_ascii<W: Words>(
+        &self,
+        words: &mut W,
+        max_list_len: usize,
+    ) -> Result<MixedPayload, RowError> {
+        self.kinds
+            .iter()
+            .map(|kind| match kind {
+                PropertyKind::Scalar(t) => next_ascii(words, *t).map(MixedValue::Scalar),
+                PropertyKind::List { prop, .. } => {
+                    let count = parse_list_len(words.next_word(), max_list_len)?;
+                    (0..count)
+                        .map(|_| next_ascii(words, *prop))
+                        .collect::<Result<_, _>>()
+                        .map(MixedValue::List)
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(MixedPayload)
     }
 
-    fn read_as_be<I: Iterator<Item = u8>>(&self, bytes: &mut I) -> Payload {
-        let count: usize = self.count.from_be_bytes(bytes).try_into().unwrap();
-        let data = (0..count).map(|_| self.prop.from_be_bytes(bytes)).collect();
-        Payload(data)
+    fn read_as_be<I: Iterator<Item = u8>>(
+        &self,
+        bytes: &mut I,
+        max_list_len: usize,
+    ) -> Result<MixedPayload, RowError> {
+        self.kinds
+            .iter()
+            .map(|kind| match kind {
+                PropertyKind::Scalar(t) => t
+                    .from_be_bytes(bytes)
+                    .map(MixedValue::Scalar)
+                    .ok_or(RowError::Eof),
+                PropertyKind::List { count, prop } => {
+                    let count = list_len(
+                        count.from_be_bytes(bytes).ok_or(RowError::Eof)?,
+                        max_list_len,
+                    )?;
+                    (0..count)
+                        .map(|_| prop.from_be_bytes(bytes).ok_or(RowError::Eof))
+                        .collect::<Result<_, _>>()
+                        .map(MixedValue::List)
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(MixedPayload)
     }
 
-    fn read_as_le<I: Iterator<Item = u8>>(&self, bytes: &mut I) -> Self::Payload {
-        let count: usize = self.count.from_be_bytes(bytes).try_into().unwrap();
-        let data = (0..count).map(|_| self.prop.from_le_bytes(bytes)).collect();
-        Payload(data)
+    fn read_as_le<I: Iterator<Item = u8>>(
+        &self,
+        bytes: &mut I,
+        max_list_len: usize,
+    ) -> Result<MixedPayload, RowError> {
+        self.kinds
+            .iter()
+            .map(|kind| match kind {
+                PropertyKind::Scalar(t) => t
+                    .from_le_bytes(bytes)
+                    .map(MixedValue::Scalar)
+                    .ok_or(RowError::Eof),
+                PropertyKind::List { count, prop } => {
+                    let count = list_len(
+                        count.from_le_bytes(bytes).ok_or(RowError::Eof)?,
+                        max_list_len,
+                    )?;
+                    (0..count)
+                        .map(|_| prop.from_le_bytes(bytes).ok_or(RowError::Eof))
+                        .collect::<Result<_, _>>()
+                        .map(MixedValue::List)
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(MixedPayload)
     }
 }
+
 #[test]
 fn test_read_as_ascii() {
     use crate::*;
@@ -370,9 +957,11 @@ fn test_read_as_ascii() {
             PLYValueTypeName::Uchar,
         ],
         names: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+        namings: vec![TypeNaming::Classic; 3],
     };
     assert_eq!(
-        prop.read_as_ascii(line),
+        prop.read_as_ascii(&mut line.split_ascii_whitespace(), usize::MAX)
+            .unwrap(),
         Payload(vec![
             PLYValue::Uchar(0),
             PLYValue::Uchar(1),
@@ -393,9 +982,11 @@ fn test_read_as_be() {
             PLYValueTypeName::Uchar,
         ],
         names: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+        namings: vec![TypeNaming::Classic; 3],
     };
     assert_eq!(
-        prop.read_as_be(&mut bytes.iter().copied()),
+        prop.read_as_be(&mut bytes.iter().copied(), usize::MAX)
+            .unwrap(),
         Payload(vec![
             PLYValue::Uchar(0),
             PLYValue::Uchar(1),
@@ -416,9 +1007,11 @@ fn test_read_as_le() {
             PLYValueTypeName::Uchar,
         ],
         names: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+        namings: vec![TypeNaming::Classic; 3],
     };
     assert_eq!(
-        prop.read_as_le(&mut bytes.iter().copied()),
+        prop.read_as_le(&mut bytes.iter().copied(), usize::MAX)
+            .unwrap(),
         Payload(vec![
             PLYValue::Uchar(0),
             PLYValue::Uchar(1),
@@ -426,3 +1019,167 @@ fn test_read_as_le() {
         ],)
     )
 }
+
+#[test]
+fn test_read_payload_errors() {
+    use super::lines::StrLines;
+    use crate::*;
+
+    let mut element = GenericElement::new(PropertyList::new(
+        "vertex_list",
+        PLYValueTypeName::Uchar,
+        PLYValueTypeName::Int,
+    ));
+    element.count = 3;
+    let lines = "2 1 2\n2 1 x\n";
+    assert_eq!(
+        read_elemet_payload_ascii(
+            "face",
+            &mut element.clone(),
+            &mut Tokens::new(StrLines::new(lines.lines().map(|e| e.to_string()))),
+            &mut Budget::default(),
+        ),
+        Err(PLYError::Payload {
+            element: "face".to_string(),
+            row: 1,
+            offset: 6,
+            reason: "cannot parse \"x\" as int".to_string()
+        })
+    );
+
+    let bytes = [1u8, 0, 0, 0, 1, 1, 0, 0];
+    assert_eq!(
+        read_element_payload_le_bytes(
+            "face",
+            &mut element,
+            &mut Bytes::new(bytes.iter().copied(), 100),
+            &mut Budget::default(),
+        ),
+        Err(PLYError::UnexpectedEof {
+            element: "face".to_string(),
+            row: 1,
+            offset: 108
+        })
+    );
+}
+
+#[test]
+fn test_skip_rows_bytes() {
+    use crate::*;
+
+    let mut props = MixedProperty::new();
+    props.push_prop("x", PLYValueTypeName::Short);
+    props.push_prop("y", PLYValueTypeName::Uchar);
+    let bytes = [0u8; 10];
+    let expected = Err(PLYError::UnexpectedEof {
+        element: "vertex".to_string(),
+        row: 3,
+        offset: 109,
+    });
+
+    // in-memory input
+    let mut in_memory = Bytes::new(bytes.iter().copied(), 100);
+    assert_eq!(
+        skip_rows_bytes("vertex", &props, 0..3, &mut in_memory, false),
+        Ok(())
+    );
+    assert_eq!(in_memory.offset(), 109);
+    let mut in_memory = Bytes::new(bytes.iter().copied(), 100);
+    assert_eq!(
+        skip_rows_bytes("vertex", &props, 0..4, &mut in_memory, false),
+        expected
+    );
+
+    // same for streamed input, consumed buffer by buffer
+    let mut streamed = Bytes::new(
+        ReadBytes::new(std::io::BufReader::with_capacity(4, &bytes[..])),
+        100,
+    );
+    assert_eq!(
+        skip_rows_bytes("vertex", &props, 0..3, &mut streamed, false),
+        Ok(())
+    );
+    assert_eq!(streamed.offset(), 109);
+    let mut streamed = Bytes::new(
+        ReadBytes::new(std::io::BufReader::with_capacity(4, &bytes[..])),
+        100,
+    );
+    assert_eq!(
+        skip_rows_bytes("vertex", &props, 0..4, &mut streamed, false),
+        expected
+    );
+}
+
+#[test]
+fn test_read_mixed_as_ascii() {
+    use crate::*;
+
+    let prop = MixedProperty::from(vec![
+        (
+            "vertex_indices",
+            PropertyKind::List {
+                count: PLYValueTypeName::Uchar,
+                prop: PLYValueTypeName::Int,
+            },
+        ),
+        ("red", PropertyKind::Scalar(PLYValueTypeName::Uchar)),
+    ]);
+    assert_eq!(
+        prop.read_as_ascii(&mut "3 0 1 2 255".split_ascii_whitespace(), usize::MAX)
+            .unwrap(),
+        MixedPayload(vec![
+            MixedValue::List(vec![PLYValue::Int(0), PLYValue::Int(1), PLYValue::Int(2)]),
+            MixedValue::Scalar(PLYValue::Uchar(255)),
+        ])
+    );
+    let mut words = "3 0 1 2 255 7".split_ascii_whitespace();
+    prop.read_as_ascii(&mut words, usize::MAX).unwrap();
+    assert_eq!(words.next(), Some("7"));
+    assert_eq!(
+        prop.read_as_ascii(&mut "3 0 1 2".split_ascii_whitespace(), usize::MAX),
+        Err(too_few_values())
+    );
+}
+
+#[test]
+fn test_read_payload_ascii_tokens() {
+    use super::lines::BufLines;
+    use crate::*;
+
+    // rows wrapped across lines, several rows on a line, CRLF
+    let mut input: &[u8] = b"3 0 1\r\n2 255 4 0 1\r\n  2 3 0\r\n\r\n3 4 5 6";
+    let mut tokens = Tokens::new(BufLines::new(&mut input));
+    let prop = MixedProperty::from(vec![
+        (
+            "vertex_indices",
+            PropertyKind::List {
+                count: PLYValueTypeName::Uchar,
+                prop: PLYValueTypeName::Int,
+            },
+        ),
+        ("red", PropertyKind::Scalar(PLYValueTypeName::Uchar)),
+    ]);
+    let mut element = GenericElement::new(prop.clone());
+    element.count = 2;
+    read_elemet_payload_ascii("face", &mut element, &mut tokens, &mut Budget::default()).unwrap();
+    assert_eq!(
+        element.payloads[1],
+        MixedPayload(vec![
+            MixedValue::List(vec![
+                PLYValue::Int(0),
+                PLYValue::Int(1),
+                PLYValue::Int(2),
+                PLYValue::Int(3)
+            ]),
+            MixedValue::Scalar(PLYValue::Uchar(0)),
+        ])
+    );
+    assert_eq!(
+        read_row_ascii("face", &prop, 2, &mut tokens, usize::MAX),
+        Err(PLYError::UnexpectedEof {
+            element: "face".to_string(),
+            row: 2,
+            offset: 31
+        })
+    );
+}
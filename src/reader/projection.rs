@@ -0,0 +1,322 @@
+use std::marker::PhantomData;
+
+use crate::{
+    error::PLYResult, Element, MixedPayload, MixedProperty, MixedValue, PLYValue, PLYValueTypeName,
+    Payload, PropertyKind,
+};
+
+use super::{
+    header::new_element,
+    limits::{Budget, DecodedBytes},
+    lines::AsciiLines,
+    payload::{
+        list_len, next_ascii, parse_list_len, read_row_ascii, read_row_be, read_row_le,
+        too_few_values, Bytes, ReadPayload, RowError,
+    },
+    tokens::{Tokens, Words},
+};
+
+/// Properties of element in file, and which of them are kept on read
+#[derive(Debug, Clone)]
+pub(crate) struct Projection {
+    props: MixedProperty,
+    keep: Vec<bool>,
+}
+
+impl Projection {
+    /// Keep properties of `names`, names not in `props` are ignored
+    pub fn new(props: MixedProperty, names: &[String]) -> Self {
+        let keep = props
+            .names
+            .iter()
+            .map(|name| names.contains(name))
+            .collect();
+        Self { props, keep }
+    }
+
+    /// Returns element `name` with kept properties only, and no payload
+    pub fn element(&self, name: String, count: usize) -> Element {
+        let mut props = MixedProperty::new();
+        for ((((name, kind), naming), count_naming), _) in self
+            .props
+            .iter()
+            .zip(self.props.namings.iter())
+            .zip(self.props.count_namings.iter())
+            .zip(self.keep.iter())
+            .filter(|(_, keep)| **keep)
+        {
+            props.push_kind(name.to_string(), kind, *naming, *count_naming);
+        }
+        new_element(name, count, props)
+    }
+
+    fn rows<T>(&self) -> ProjectedRows<'_, T> {
+        ProjectedRows {
+            projection: self,
+            _payload: PhantomData,
+        }
+    }
+
+    fn read_bytes<T, I>(
+        &self,
+        bytes: &mut I,
+        decode: fn(&PLYValueTypeName, &mut I) -> Option<PLYValue>,
+        max_list_len: usize,
+    ) -> Result<T, RowError>
+    where
+        T: ProjectedPayload,
+        I: Iterator<Item = u8>,
+    {
+        let mut payload = T::default();
+        for (kind, keep) in self.props.kinds.iter().zip(self.keep.iter()) {
+            match (kind, keep) {
+                (PropertyKind::Scalar(t), true) => {
+                    payload.push_scalar(decode(t, bytes).ok_or(RowError::Eof)?)
+                }
+                (PropertyKind::Scalar(t), false) => skip_bytes(bytes, t.bytes_len())?,
+                (PropertyKind::List { count, prop }, true) => {
+                    let len = list_len(decode(count, bytes).ok_or(RowError::Eof)?, max_list_len)?;
+                    payload.push_list(
+                        (0..len)
+                            .map(|_| decode(prop, bytes).ok_or(RowError::Eof))
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                (PropertyKind::List { count, prop }, false) => {
+                    let len = list_len(decode(count, bytes).ok_or(RowError::Eof)?, usize::MAX)?;
+                    skip_bytes(bytes, len.saturating_mul(prop.bytes_len()))?;
+                }
+            }
+        }
+        Ok(payload)
+    }
+}
+
+fn skip_bytes<I: Iterator<Item = u8>>(bytes: &mut I, n: usize) -> Result<(), RowError> {
+    if n > 0 && bytes.nth(n - 1).is_none() {
+        return Err(RowError::Eof);
+    }
+    Ok(())
+}
+
+/// Payload of projected element
+trait ProjectedPayload: Default {
+    fn push_scalar(&mut self, value: PLYValue);
+    fn push_list(&mut self, values: Vec<PLYValue>);
+}
+
+/// [Payload] holds either scalar values, or values of the only list
+impl ProjectedPayload for Payload {
+    fn push_scalar(&mut self, value: PLYValue) {
+        self.0.push(value);
+    }
+    fn push_list(&mut self, values: Vec<PLYValue>) {
+        self.0.extend(values);
+    }
+}
+
+impl ProjectedPayload for MixedPayload {
+    fn push_scalar(&mut self, value: PLYValue) {
+        self.push_value(MixedValue::Scalar(value));
+    }
+    fn push_list(&mut self, values: Vec<PLYValue>) {
+        self.push_value(MixedValue::List(values));
+    }
+}
+
+/// Reads rows of projected element as payload `T`
+///
+/// Values of properties which are not kept are consumed without being parsed or decoded.
+struct ProjectedRows<'a, T> {
+    projection: &'a Projection,
+    _payload: PhantomData<T>,
+}
+
+impl<'a, T: ProjectedPayload> ReadPayload for ProjectedRows<'a, T> {
+    type Payload = T;
+
+    fn read_as_ascii<W: Words>(&self, words: &mut W, max_list_len: usize) -> Result<T, RowError> {
+        let mut payload = T::default();
+        let projection = self.projection;
+        for (kind, keep) in projection.props.kinds.iter().zip(projection.keep.iter()) {
+            match (kind, keep) {
+                (PropertyKind::Scalar(t), true) => payload.push_scalar(next_ascii(words, *t)?),
+                (PropertyKind::Scalar(_), false) => {
+                    words.next_word().ok_or_else(too_few_values)?;
+                }
+                (PropertyKind::List { prop, .. }, true) => {
+                    let len = parse_list_len(words.next_word(), max_list_len)?;
+                    payload.push_list(
+                        (0..len)
+                            .map(|_| next_ascii(words, *prop))
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                (PropertyKind::List { .. }, false) => {
+                    let len = parse_list_len(words.next_word(), usize::MAX)?;
+                    if !words.skip_words(len) {
+                        return Err(too_few_values());
+                    }
+                }
+            }
+        }
+        Ok(payload)
+    }
+
+    fn read_as_be<I: Iterator<Item = u8>>(
+        &self,
+        bytes: &mut I,
+        max_list_len: usize,
+    ) -> Result<T, RowError> {
+        self.projection
+            .read_bytes(bytes, PLYValueTypeName::from_be_bytes, max_list_len)
+    }
+
+    fn read_as_le<I: Iterator<Item = u8>>(
+        &self,
+        bytes: &mut I,
+        max_list_len: usize,
+    ) -> Result<T, RowError> {
+        self.projection
+            .read_bytes(bytes, PLYValueTypeName::from_le_bytes, max_list_len)
+    }
+}
+
+/// Reads one row of `rows` from ascii tokens or binary bytes
+trait RowSource {
+    fn read_row<P: ReadPayload>(
+        &mut self,
+        name: &str,
+        rows: &P,
+        row: usize,
+        max_list_len: usize,
+    ) -> PLYResult<P::Payload>;
+}
+
+struct Ascii<'a, L>(&'a mut Tokens<L>);
+
+impl<L: AsciiLines> RowSource for Ascii<'_, L> {
+    fn read_row<P: ReadPayload>(
+        &mut self,
+        name: &str,
+        rows: &P,
+        row: usize,
+        max_list_len: usize,
+    ) -> PLYResult<P::Payload> {
+        read_row_ascii(name, rows, row, self.0, max_list_len)
+    }
+}
+
+struct BigEndian<'a, I>(&'a mut Bytes<I>);
+
+impl<I: Iterator<Item = u8>> RowSource for BigEndian<'_, I> {
+    fn read_row<P: ReadPayload>(
+        &mut self,
+        name: &str,
+        rows: &P,
+        row: usize,
+        max_list_len: usize,
+    ) -> PLYResult<P::Payload> {
+        read_row_be(name, rows, row, self.0, max_list_len)
+    }
+}
+
+struct LittleEndian<'a, I>(&'a mut Bytes<I>);
+
+impl<I: Iterator<Item = u8>> RowSource for LittleEndian<'_, I> {
+    fn read_row<P: ReadPayload>(
+        &mut self,
+        name: &str,
+        rows: &P,
+        row: usize,
+        max_list_len: usize,
+    ) -> PLYResult<P::Payload> {
+        read_row_le(name, rows, row, self.0, max_list_len)
+    }
+}
+
+/// Read all rows of `element` created by [Projection::element] from `source`
+fn read_projected<S: RowSource>(
+    element: &mut Element,
+    projection: &Projection,
+    source: &mut S,
+    budget: &mut Budget,
+) -> PLYResult<()> {
+    fn read_rows<T, S>(
+        name: &str,
+        count: usize,
+        payloads: &mut Vec<T>,
+        projection: &Projection,
+        source: &mut S,
+        budget: &mut Budget,
+    ) -> PLYResult<()>
+    where
+        T: ProjectedPayload + DecodedBytes,
+        S: RowSource,
+    {
+        let rows = projection.rows::<T>();
+        for row in 0..count {
+            let payload = source.read_row(name, &rows, row, budget.max_list_len())?;
+            budget.decode(&payload)?;
+            payloads.push(payload);
+        }
+        Ok(())
+    }
+
+    match element {
+        Element::Element { name, elements } => read_rows(
+            name,
+            elements.count,
+            &mut elements.payloads,
+            projection,
+            source,
+            budget,
+        ),
+        Element::ListElement { name, elements } => read_rows(
+            name,
+            elements.count,
+            &mut elements.payloads,
+            projection,
+            source,
+            budget,
+        ),
+        Element::MixedElement { name, elements } => read_rows(
+            name,
+            elements.count,
+            &mut elements.payloads,
+            projection,
+            source,
+            budget,
+        ),
+    }
+}
+
+/// Read payload of `element` created by [Projection::element]
+pub(crate) fn read_projected_ascii<L: AsciiLines>(
+    element: &mut Element,
+    projection: &Projection,
+    tokens: &mut Tokens<L>,
+    budget: &mut Budget,
+) -> PLYResult<()> {
+    read_projected(element, projection, &mut Ascii(tokens), budget)
+}
+
+/// Read binary big endian payload of `element` created by [Projection::element]
+pub(crate) fn read_projected_be<I: Iterator<Item = u8>>(
+    element: &mut Element,
+    projection: &Projection,
+    bytes: &mut Bytes<I>,
+    budget: &mut Budget,
+) -> PLYResult<()> {
+    read_projected(element, projection, &mut BigEndian(bytes), budget)
+}
+
+/// Read binary little endian payload of `element` created by [Projection::element]
+pub(crate) fn read_projected_le<I: Iterator<Item = u8>>(
+    element: &mut Element,
+    projection: &Projection,
+    bytes: &mut Bytes<I>,
+    budget: &mut Budget,
+) -> PLYResult<()> {
+    read_projected(element, projection, &mut LittleEndian(bytes), budget)
+}
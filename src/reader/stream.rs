@@ -0,0 +1,417 @@
+use std::io::BufRead;
+
+use crate::{error::PLYResult, Format, Limits, MixedPayload, MixedProperty, PLYFile};
+
+use super::{
+    header::read_header_with,
+    limits::Budget,
+    lines::BufLines,
+    payload::{
+        io_error_or, read_row_ascii, read_row_be, read_row_le, skip_rows_ascii, skip_rows_bytes,
+        Bytes, ReadBytes,
+    },
+    tokens::Tokens,
+    warnings::Warnings,
+};
+
+enum Body<R> {
+    Ascii(Tokens<BufLines<R>>),
+    BigEndian(Bytes<ReadBytes<R>>),
+    LittleEndian(Bytes<ReadBytes<R>>),
+}
+
+/// Element being read by [PLYReader]
+pub(super) struct Current {
+    pub index: usize,
+    pub props: MixedProperty,
+    pub row: usize,
+}
+
+/// Streaming PLY reader
+///
+/// Header is read on creation, then rows are read element by element on demand,
+/// so memory usage does not depend on the number of rows.
+/// Input is read in lenient [ParseMode](crate::ParseMode), warnings are not reported.
+/// For input which is not trusted, create it by [PLYReader::with_limits].
+///
+/// After a row or skipped rows fail to read, the reader is failed and returns no more rows or elements,
+/// as the position in input is no longer known.
+///
+/// ```no_run
+/// # fn main() -> ply::error::PLYResult<()> {
+/// use std::{fs::File, io::BufReader};
+///
+/// let mut reader = ply::PLYReader::new(BufReader::new(File::open("mesh.ply")?))?;
+/// while let Some(element) = reader.next_element()? {
+///     let name = element.name().to_string();
+///     let mut rows = 0;
+///     for row in element {
+///         let _row = row?;
+///         rows += 1;
+///     }
+///     println!("{name}: {rows} rows");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct PLYReader<R> {
+    header: PLYFile,
+    body: Body<R>,
+    current: Option<Current>,
+    next: usize,
+    budget: Budget,
+    /// an error was returned, nothing is read after it
+    failed: bool,
+}
+
+impl<R: BufRead> PLYReader<R> {
+    /// Read header from `reader`, payload is not read yet
+    pub fn new(reader: R) -> PLYResult<Self> {
+        PLYReader::with_limits(reader, Limits::default())
+    }
+
+    /// Read header from `reader` checking it against `limits`, payload is not read yet
+    ///
+    /// Rows read are checked against `max_list_len` and `max_decoded_bytes` of `limits`,
+    /// rows skipped are not decoded and not counted.
+    pub fn with_limits(reader: R, limits: Limits) -> PLYResult<Self> {
+        let mut lines = BufLines::new(reader);
+        let header = read_header_with(&mut lines, &mut Warnings::default(), &limits)?;
+        let body = match header.format {
+            Format::Ascii { .. } => Body::Ascii(Tokens::new(lines)),
+            Format::BinaryBigEndian { .. } => {
+                let offset = lines.position();
+                Body::BigEndian(Bytes::new(ReadBytes::new(lines.into_inner()), offset))
+            }
+            Format::BinaryLittleEndian { .. } => {
+                let offset = lines.position();
+                Body::LittleEndian(Bytes::new(ReadBytes::new(lines.into_inner()), offset))
+            }
+        };
+        Ok(Self {
+            header,
+            body,
+            current: None,
+            next: 0,
+            budget: Budget::new(limits),
+            failed: false,
+        })
+    }
+
+    /// Returns parsed header
+    ///
+    /// Elements have counts and properties declared in header, but no payload.
+    pub fn header(&self) -> &PLYFile {
+        &self.header
+    }
+
+    /// Returns reader of the next element, or None after the last element
+    ///
+    /// Rows of the previous element which were not read are skipped.
+    pub fn next_element(&mut self) -> PLYResult<Option<ElementReader<'_, R>>> {
+        let skipped = self.skip_rows();
+        self.fail_on(&skipped);
+        skipped?;
+        if self.failed || self.next >= self.header.elements.len() {
+            self.current = None;
+            return Ok(None);
+        }
+        self.current = Some(Current {
+            index: self.next,
+            props: self.header.elements[self.next].to_mixed_property(),
+            row: 0,
+        });
+        self.next += 1;
+        Ok(Some(ElementReader { reader: self }))
+    }
+
+    /// Skip rows of current element which were not read, without decoding them
+    fn skip_rows(&mut self) -> PLYResult<()> {
+        let current = match &mut self.current {
+            Some(current) if !self.failed => current,
+            _ => return Ok(()),
+        };
+        let element = &self.header.elements[current.index];
+        let rows = current.row..element.count();
+        let name = element.name();
+        match &mut self.body {
+            Body::Ascii(tokens) => skip_rows_ascii(name, &current.props, rows, tokens),
+            Body::BigEndian(bytes) => skip_rows_bytes(name, &current.props, rows, bytes, true)
+                .map_err(|e| io_error_or(bytes, e)),
+            Body::LittleEndian(bytes) => skip_rows_bytes(name, &current.props, rows, bytes, false)
+                .map_err(|e| io_error_or(bytes, e)),
+        }?;
+        current.row = element.count();
+        Ok(())
+    }
+
+    /// Mark reader as failed if `result` is an error
+    fn fail_on<T>(&mut self, result: &PLYResult<T>) {
+        if result.is_err() {
+            self.failed = true;
+        }
+    }
+
+    fn read_row(&mut self) -> PLYResult<Option<MixedPayload>> {
+        let row = self.try_read_row();
+        self.fail_on(&row);
+        row
+    }
+
+    fn try_read_row(&mut self) -> PLYResult<Option<MixedPayload>> {
+        let current = match &mut self.current {
+            Some(current) if !self.failed => current,
+            _ => return Ok(None),
+        };
+        let element = &self.header.elements[current.index];
+        if current.row >= element.count() {
+            return Ok(None);
+        }
+        let name = element.name();
+        let max_list_len = self.budget.max_list_len();
+        let payload = match &mut self.body {
+            Body::Ascii(tokens) => {
+                read_row_ascii(name, &current.props, current.row, tokens, max_list_len)
+            }
+            Body::BigEndian(bytes) => {
+                read_row_be(name, &current.props, current.row, bytes, max_list_len)
+                    .map_err(|e| io_error_or(bytes, e))
+            }
+            Body::LittleEndian(bytes) => {
+                read_row_le(name, &current.props, current.row, bytes, max_list_len)
+                    .map_err(|e| io_error_or(bytes, e))
+            }
+        }?;
+        self.budget.decode(&payload)?;
+        current.row += 1;
+        Ok(Some(payload))
+    }
+}
+
+/// Rows of one element, returned by [PLYReader::next_element]
+///
+/// Iterates over rows as [MixedPayload], in the property order of the element.
+pub struct ElementReader<'a, R> {
+    reader: &'a mut PLYReader<R>,
+}
+
+impl<'a, R: BufRead> ElementReader<'a, R> {
+    fn current(&self) -> &Current {
+        self.reader
+            .current
+            .as_ref()
+            .expect("ElementReader exists only while an element is being read")
+    }
+
+    /// Returns element name
+    pub fn name(&self) -> &str {
+        self.reader.header.elements[self.current().index].name()
+    }
+
+    /// Returns number of rows declared in header
+    pub fn row_count(&self) -> usize {
+        self.reader.header.elements[self.current().index].count()
+    }
+
+    /// Returns properties of element
+    pub fn property(&self) -> &MixedProperty {
+        &self.current().props
+    }
+
+    /// Read next row, or None after the last row or an error
+    pub fn next_row(&mut self) -> PLYResult<Option<MixedPayload>> {
+        self.reader.read_row()
+    }
+}
+
+impl<'a, R: BufRead> Iterator for ElementReader<'a, R> {
+    type Item = PLYResult<MixedPayload>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_row().transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PLYReader;
+    use crate::{
+        error::{Limit, PLYError},
+        Limits, MixedPayload, MixedValue, PLYValue,
+    };
+
+    const ASCII: &str = "\
+ply
+format ascii 1.0
+element vertex 3
+property float x
+property uchar red
+element face 2
+property list uchar int vertex_indices
+property uchar red
+end_header
+0 1
+1.5 2
+2 3
+3 0 1 2 9
+2 1 2 8
+";
+
+    #[test]
+    fn test_stream_ascii() {
+        let mut reader = PLYReader::new(ASCII.as_bytes()).unwrap();
+        assert_eq!(reader.header().elements.len(), 2);
+        assert_eq!(reader.header().elements[0].count(), 3);
+
+        let vertex = reader.next_element().unwrap().unwrap();
+        assert_eq!(vertex.name(), "vertex");
+        assert_eq!(vertex.row_count(), 3);
+        let sum: f32 = vertex
+            .map(|row| row.unwrap()[0].as_scalar().unwrap())
+            .map(|v| match v {
+                PLYValue::Float(f) => f,
+                _ => unreachable!(),
+            })
+            .sum();
+        assert_eq!(sum, 3.5);
+
+        let mut face = reader.next_element().unwrap().unwrap();
+        assert_eq!(face.name(), "face");
+        assert_eq!(
+            face.next_row().unwrap(),
+            Some(MixedPayload::new(vec![
+                MixedValue::List(vec![PLYValue::Int(0), PLYValue::Int(1), PLYValue::Int(2)]),
+                MixedValue::Scalar(PLYValue::Uchar(9)),
+            ]))
+        );
+        assert_eq!(face.row_count(), 2);
+        assert!(face.next_row().unwrap().is_some());
+        assert_eq!(face.next_row().unwrap(), None);
+        assert!(reader.next_element().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stream_skips_unread_rows() {
+        let mut reader = PLYReader::new(ASCII.as_bytes()).unwrap();
+        let mut vertex = reader.next_element().unwrap().unwrap();
+        assert!(vertex.next_row().unwrap().is_some());
+        let face = reader.next_element().unwrap().unwrap();
+        assert_eq!(face.name(), "face");
+        assert_eq!(face.row_count(), 2);
+        assert_eq!(face.filter_map(Result::ok).count(), 2);
+    }
+
+    #[test]
+    fn test_stream_binary() {
+        let mut input = b"ply
+format binary_little_endian 1.0
+element vertex 2
+property short x
+element face 1
+property list uchar int vertex_indices
+end_header
+"
+        .to_vec();
+        input.extend_from_slice(&10i16.to_le_bytes());
+        input.extend_from_slice(&(-1i16).to_le_bytes());
+        input.push(1);
+        input.extend_from_slice(&0x0A0Ai32.to_le_bytes());
+
+        let mut reader = PLYReader::new(&input[..]).unwrap();
+        let rows = reader
+            .next_element()
+            .unwrap()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                MixedPayload::new(vec![MixedValue::Scalar(PLYValue::Short(10))]),
+                MixedPayload::new(vec![MixedValue::Scalar(PLYValue::Short(-1))]),
+            ]
+        );
+        let rows = reader
+            .next_element()
+            .unwrap()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![MixedPayload::new(vec![MixedValue::List(vec![
+                PLYValue::Int(0x0A0A)
+            ])])]
+        );
+
+        // truncated inside the face row
+        let header_len = input.len() - 9;
+        let mut reader = PLYReader::new(&input[..input.len() - 2]).unwrap();
+        reader.next_element().unwrap();
+        let mut face = reader.next_element().unwrap().unwrap();
+        assert_eq!(
+            face.next_row(),
+            Err(PLYError::UnexpectedEof {
+                element: "face".to_string(),
+                row: 0,
+                offset: header_len + 7,
+            })
+        );
+        assert_eq!(face.next_row(), Ok(None));
+        assert!(reader.next_element().unwrap().is_none());
+
+        // iteration ends after the first error
+        let mut reader = PLYReader::new(&input[..input.len() - 2]).unwrap();
+        reader.next_element().unwrap();
+        let face = reader.next_element().unwrap().unwrap();
+        assert_eq!(face.filter_map(Result::ok).count(), 0);
+    }
+
+    #[test]
+    fn test_stream_stops_after_error() {
+        let input = ASCII.replace("1.5 2", "1.5 x");
+        let mut reader = PLYReader::new(input.as_bytes()).unwrap();
+        let vertex = reader.next_element().unwrap().unwrap();
+        let rows = vertex.collect::<Vec<_>>();
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].is_ok());
+        assert!(matches!(rows[1], Err(PLYError::Payload { row: 1, .. })));
+        // rows are not read from the middle of the bad row
+        assert!(reader.next_element().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stream_limits() {
+        let exceeded = |limit, max, found| PLYError::LimitExceeded { limit, max, found };
+        let limits = Limits {
+            max_header_lines: Some(8),
+            ..Limits::default()
+        };
+        assert_eq!(
+            PLYReader::with_limits(ASCII.as_bytes(), limits).err(),
+            Some(exceeded(Limit::HeaderLines, 8, 9))
+        );
+
+        let limits = Limits {
+            max_list_len: Some(2),
+            max_decoded_bytes: Some(16),
+            ..Limits::default()
+        };
+        let mut reader = PLYReader::with_limits(ASCII.as_bytes(), limits).unwrap();
+        // vertex rows are skipped without being counted
+        reader.next_element().unwrap();
+        let mut face = reader.next_element().unwrap().unwrap();
+        assert_eq!(face.next_row(), Err(exceeded(Limit::ListLength, 2, 3)));
+
+        let limits = Limits {
+            max_decoded_bytes: Some(12),
+            ..Limits::default()
+        };
+        let mut reader = PLYReader::with_limits(ASCII.as_bytes(), limits).unwrap();
+        let vertex = reader.next_element().unwrap().unwrap();
+        assert_eq!(
+            vertex.collect::<Result<Vec<_>, _>>(),
+            Err(exceeded(Limit::DecodedBytes, 12, 15))
+        );
+    }
+}
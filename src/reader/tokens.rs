@@ -0,0 +1,153 @@
+use std::str::SplitAsciiWhitespace;
+
+use crate::error::{PLYError, PLYResult};
+
+use super::{lines::AsciiLines, payload::RowError};
+
+/// Source of whitespace separated words of ascii payload
+pub(crate) trait Words {
+    /// Returns next word, or None if there are no more words
+    fn next_word(&mut self) -> Option<&str>;
+
+    /// Skip `n` words, returns false if words ended before
+    fn skip_words(&mut self, n: usize) -> bool {
+        (0..n).all(|_| self.next_word().is_some())
+    }
+}
+
+/// Words of a single line
+impl<'a> Words for SplitAsciiWhitespace<'a> {
+    fn next_word(&mut self) -> Option<&str> {
+        self.next()
+    }
+}
+
+/// Words of ascii payload read across lines
+///
+/// Rows are delimited by properties of element rather than by line breaks,
+/// so a row may span several lines and a line may hold several rows.
+/// Iteration stops at the first error of underlying lines, which is kept to be reported instead of EOF.
+pub(crate) struct Tokens<L> {
+    lines: L,
+    /// current line without line terminator
+    line: String,
+    line_offset: usize,
+    /// byte position in `line` of the next character to read
+    pos: usize,
+    ended: bool,
+    error: Option<PLYError>,
+}
+
+impl<L: AsciiLines> Tokens<L> {
+    pub fn new(lines: L) -> Self {
+        let line_offset = lines.line_offset();
+        Self {
+            lines,
+            line: String::new(),
+            line_offset,
+            pos: 0,
+            ended: false,
+            error: None,
+        }
+    }
+
+    /// Returns byte offset of the next character, or the end of input after words ended
+    pub fn offset(&self) -> usize {
+        self.line_offset + self.pos
+    }
+
+    /// Skip whitespace and line breaks, returns false if no word follows
+    pub fn skip_whitespace(&mut self) -> PLYResult<bool> {
+        loop {
+            let rest = &self.line[self.pos..];
+            let trimmed = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+            self.pos += rest.len() - trimmed.len();
+            if !trimmed.is_empty() {
+                return Ok(true);
+            }
+            if !self.read_line()? {
+                return Ok(false);
+            }
+        }
+    }
+
+    /// Read next line into `line`, returns false at the end of input
+    fn read_line(&mut self) -> PLYResult<bool> {
+        self.line.clear();
+        self.pos = 0;
+        let read = match self.lines.next_line()? {
+            Some(line) => {
+                self.line.push_str(line);
+                true
+            }
+            None => false,
+        };
+        self.line_offset = self.lines.line_offset();
+        self.ended = !read;
+        Ok(read)
+    }
+
+    /// Returns true if nothing but whitespace follows in the last line of input,
+    /// and the line has no line terminator, so input may be cut in the middle of word
+    pub fn is_at_unterminated_end(&self) -> bool {
+        !self.lines.is_line_terminated()
+            && self.line[self.pos..]
+                .trim_start_matches(|c: char| c.is_ascii_whitespace())
+                .is_empty()
+    }
+
+    /// Convert error of row starting at `offset` into [PLYError]
+    ///
+    /// Missing values at the end of input are [RowError::Eof], error of underlying lines is returned as is.
+    pub fn row_error(&mut self, e: RowError, name: &str, row: usize, offset: usize) -> PLYError {
+        match self.error.take() {
+            Some(error) => error,
+            None if self.ended => RowError::Eof.at(name, row, offset),
+            None => e.at(name, row, offset),
+        }
+    }
+}
+
+impl<L: AsciiLines> Words for Tokens<L> {
+    fn next_word(&mut self) -> Option<&str> {
+        match self.skip_whitespace() {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(e) => {
+                self.error = Some(e);
+                self.ended = true;
+                return None;
+            }
+        }
+        let start = self.pos;
+        let len = self.line[start..]
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(self.line.len() - start);
+        self.pos += len;
+        Some(&self.line[start..start + len])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Tokens, Words};
+    use crate::reader::lines::BufLines;
+
+    #[test]
+    fn test_tokens_across_lines() {
+        let mut input: &[u8] = b"3 0\r\n  1\t2 4\n\n5 \r\n6";
+        let mut tokens = Tokens::new(BufLines::new(&mut input));
+        assert_eq!(tokens.next_word(), Some("3"));
+        assert_eq!(tokens.next_word(), Some("0"));
+        assert!(tokens.skip_whitespace().unwrap());
+        assert_eq!(tokens.offset(), 7);
+        assert_eq!(tokens.next_word(), Some("1"));
+        assert!(tokens.skip_words(2));
+        assert_eq!(tokens.next_word(), Some("5"));
+        assert!(!tokens.is_at_unterminated_end());
+        assert_eq!(tokens.next_word(), Some("6"));
+        assert!(tokens.is_at_unterminated_end());
+        assert_eq!(tokens.next_word(), None);
+        assert_eq!(tokens.offset(), 19);
+    }
+}
@@ -0,0 +1,34 @@
+use crate::error::{PLYResult, PLYWarning};
+
+use super::ParseMode;
+
+/// Collects [PLYWarning]s in lenient mode, or rejects them in strict mode
+#[derive(Debug, Default)]
+pub(crate) struct Warnings {
+    mode: ParseMode,
+    warnings: Vec<PLYWarning>,
+}
+
+impl Warnings {
+    pub fn new(mode: ParseMode) -> Self {
+        Self {
+            mode,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Returns error of `warning` in strict mode
+    pub fn warn(&mut self, warning: PLYWarning) -> PLYResult<()> {
+        match self.mode {
+            ParseMode::Lenient => {
+                self.warnings.push(warning);
+                Ok(())
+            }
+            ParseMode::Strict => Err(warning.into()),
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<PLYWarning> {
+        self.warnings
+    }
+}
@@ -1,7 +1,29 @@
 //! PLY File Writer
 
+use crate::{AsciiOptions, Compression, TypeNaming};
+
+pub(crate) mod ascii;
+#[cfg(feature = "async")]
+pub(crate) mod asynchronous;
 pub(crate) mod header;
 pub(crate) mod payload;
+pub(crate) mod stream;
+pub(crate) mod validate;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// Options for [PLYFile::write_with_options](crate::PLYFile::write_with_options)
+pub struct WriteOptions {
+    /// Naming of type names in header.
+    ///
+    /// None keeps naming of each property (as read from file, or [TypeNaming::Classic]).
+    pub type_naming: Option<TypeNaming>,
+    /// Compress whole output, header included.
+    ///
+    /// [Compression::Gzip] and [Compression::Zstd] require feature `gzip` and `zstd`.
+    pub compression: Compression,
+    /// Notation of float values in ascii payload, shortest round-trip by default.
+    pub ascii: AsciiOptions,
+}
 
 #[cfg(test)]
 mod test {
@@ -22,6 +44,7 @@ mod test {
                             PLYValueTypeName::Float,
                         ],
                         names: vec!["x".to_string(), "y".to_string(), "z".to_string()],
+                        namings: vec![TypeNaming::Classic; 3],
                     },
                     payloads: vec![
                         Payload(vec![
@@ -75,6 +98,8 @@ mod test {
                         count: PLYValueTypeName::Uchar,
                         prop: PLYValueTypeName::Char,
                         name: "vertex_id".to_string(),
+                        count_naming: TypeNaming::Classic,
+                        naming: TypeNaming::Classic,
                     },
                     payloads: vec![
                         Payload(vec![PLYValue::Char(3)]),
@@ -92,6 +117,8 @@ mod test {
                     version: "1.0".to_string(),
                 },
                 comments: vec![Comment(vec!["test".to_string(), "data".to_string()])],
+                obj_infos: Vec::new(),
+                obj_info_positions: Vec::new(),
                 elements: vec![element_vertex, element_list],
             }
         };
@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, LowerExp},
+};
+
+use crate::{PLYValue, PLYValueTypeName};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Notation of `float` and `double` values in ascii payload
+pub enum FloatFormat {
+    /// Shortest decimal which is read back as the same value, like `0.1` or `1500`
+    ///
+    /// Output of this format is read back by [PLYFile](crate::PLYFile) bit-exact,
+    /// infinities are written as `inf` and `-inf`, NaN as `NaN` and `-NaN`.
+    /// PLY has no notation for payload bits of NaN, so NaN other than [f32::NAN] or [f64::NAN]
+    /// is read back as one of them with the same sign.
+    #[default]
+    Shortest,
+    /// Fixed number of digits after decimal point, like `0.100` for `Fixed(3)`
+    Fixed(usize),
+    /// Scientific notation like `1.5e3`, with fixed number of digits after decimal point if Some
+    Scientific(Option<usize>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// Formatting of ascii payload, see [WriteOptions::ascii](crate::WriteOptions::ascii)
+///
+/// Integer values are always written as decimal integers.
+pub struct AsciiOptions {
+    /// Format of `float` values
+    pub float: FloatFormat,
+    /// Format of `double` values
+    pub double: FloatFormat,
+    /// Format of values of property by its name in any element, instead of format of its type
+    ///
+    /// Applies to values of list property too.
+    pub properties: HashMap<String, FloatFormat>,
+}
+
+impl AsciiOptions {
+    /// Format of values of property `name` of type `ty`
+    pub(crate) fn format_of(&self, name: &str, ty: PLYValueTypeName) -> FloatFormat {
+        match (self.properties.get(name), ty) {
+            (Some(format), _) => *format,
+            (None, PLYValueTypeName::Float) => self.float,
+            (None, PLYValueTypeName::Double) => self.double,
+            (None, _) => FloatFormat::Shortest,
+        }
+    }
+}
+
+/// Displays value in ascii payload with `format`
+pub(crate) struct AsciiValue<'a>(pub &'a PLYValue, pub FloatFormat);
+
+impl Display for AsciiValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            PLYValue::Float(v) => write_float(f, *v, v.is_nan() && v.is_sign_negative(), self.1),
+            PLYValue::Double(v) => write_float(f, *v, v.is_nan() && v.is_sign_negative(), self.1),
+            v => write!(f, "{v}"),
+        }
+    }
+}
+
+/// `-` of negative NaN is not written by [Display] of floats
+fn write_float<F: Display + LowerExp>(
+    f: &mut fmt::Formatter,
+    v: F,
+    negative_nan: bool,
+    format: FloatFormat,
+) -> fmt::Result {
+    if negative_nan {
+        write!(f, "-")?;
+    }
+    match format {
+        FloatFormat::Shortest => write!(f, "{v}"),
+        FloatFormat::Fixed(precision) => write!(f, "{v:.precision$}"),
+        FloatFormat::Scientific(None) => write!(f, "{v:e}"),
+        FloatFormat::Scientific(Some(precision)) => write!(f, "{v:.precision$e}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::BufWriter;
+
+    use super::{AsciiOptions, AsciiValue, FloatFormat};
+    use crate::{
+        Element, Format, GenericElement, PLYFile, PLYValue, Payload, Property, WriteOptions,
+    };
+
+    #[test]
+    fn test_float_format() {
+        let cases = [
+            (FloatFormat::Shortest, PLYValue::Float(0.1), "0.1"),
+            (FloatFormat::Shortest, PLYValue::Double(1500.0), "1500"),
+            (FloatFormat::Shortest, PLYValue::Float(-f32::NAN), "-NaN"),
+            (FloatFormat::Fixed(3), PLYValue::Float(0.1), "0.100"),
+            (FloatFormat::Fixed(0), PLYValue::Double(2.5), "2"),
+            (FloatFormat::Fixed(2), PLYValue::Float(f32::INFINITY), "inf"),
+            (
+                FloatFormat::Scientific(None),
+                PLYValue::Double(1500.0),
+                "1.5e3",
+            ),
+            (
+                FloatFormat::Scientific(Some(2)),
+                PLYValue::Float(0.1),
+                "1.00e-1",
+            ),
+            (FloatFormat::Fixed(3), PLYValue::Int(-7), "-7"),
+        ];
+        for (format, value, expected) in cases {
+            assert_eq!(AsciiValue(&value, format).to_string(), expected);
+        }
+    }
+
+    fn ascii(values: Vec<(&str, PLYValue)>) -> PLYFile {
+        let mut props = Property::new();
+        for (name, value) in values.iter() {
+            props.push_prop(*name, value.value_type());
+        }
+        let mut element = GenericElement::new(props);
+        element
+            .push_payload(Payload::new(values.into_iter().map(|(_, v)| v).collect()))
+            .unwrap();
+        let mut ply = PLYFile::new(Format::Ascii {
+            version: "1.0".to_string(),
+        });
+        ply.elements.push(Element::Element {
+            name: "vertex".to_string(),
+            elements: element,
+        });
+        ply
+    }
+
+    fn write(ply: &PLYFile, options: &WriteOptions) -> Vec<u8> {
+        let mut writer = BufWriter::new(Vec::new());
+        ply.write_with_options(&mut writer, options).unwrap();
+        writer.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_ascii_round_trip() {
+        let floats = [
+            0.1,
+            -0.0,
+            f32::MIN_POSITIVE,
+            1e-45,
+            f32::MAX,
+            f32::EPSILON,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NAN,
+            -f32::NAN,
+        ];
+        let doubles = [
+            0.1,
+            -0.0,
+            f64::MIN_POSITIVE,
+            5e-324,
+            f64::MAX,
+            1.0 / 3.0,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NAN,
+            -f64::NAN,
+        ];
+        let names = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+        let mut values = Vec::new();
+        for (name, v) in names.iter().zip(floats) {
+            values.push((*name, PLYValue::Float(v)));
+        }
+        let double_names = ["k", "l", "m", "n", "o", "p", "q", "r", "s", "t"];
+        for (name, v) in double_names.iter().zip(doubles) {
+            values.push((*name, PLYValue::Double(v)));
+        }
+        let ply = ascii(values);
+
+        let read = PLYFile::from_bytes(&write(&ply, &WriteOptions::default())).unwrap();
+        let bits = |ply: &PLYFile| match &ply.elements[0] {
+            Element::Element { elements, .. } => elements.payload()[0]
+                .iter()
+                .map(|v| match v {
+                    PLYValue::Float(v) => v.to_bits() as u64,
+                    PLYValue::Double(v) => v.to_bits(),
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>(),
+            _ => unreachable!(),
+        };
+        assert_eq!(bits(&read), bits(&ply));
+    }
+
+    #[test]
+    fn test_ascii_options() {
+        let ply = ascii(vec![
+            ("x", PLYValue::Float(0.125)),
+            ("y", PLYValue::Float(1500.0)),
+            ("nx", PLYValue::Double(0.5)),
+            ("red", PLYValue::Uchar(255)),
+        ]);
+        let mut options = WriteOptions {
+            ascii: AsciiOptions {
+                float: FloatFormat::Fixed(2),
+                double: FloatFormat::Scientific(Some(1)),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let payload = |data: Vec<u8>| {
+            let text = String::from_utf8(data).unwrap();
+            text.lines().last().unwrap().to_string()
+        };
+        assert_eq!(payload(write(&ply, &options)), "0.12 1500.00 5.0e-1 255");
+
+        options
+            .ascii
+            .properties
+            .insert("y".to_string(), FloatFormat::Scientific(None));
+        assert_eq!(payload(write(&ply, &options)), "0.12 1.5e3 5.0e-1 255");
+    }
+}
@@ -0,0 +1,117 @@
+//! Async writer for tokio (feature `async`)
+
+use std::io::{self, BufWriter};
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::{AsciiOptions, Compression, Element, Format, GenericElement, PLYFile};
+
+use super::{
+    header::PlyWriteHeader,
+    payload::{encode_row, WritePayload},
+    WriteOptions,
+};
+
+/// Encoded bytes are written to async writer when buffer reaches this size
+const CHUNK_BYTES: usize = 64 * 1024;
+
+impl PLYFile {
+    /// Async counterpart of [PLYFile::write]
+    pub async fn write_async<W: AsyncWrite + Unpin>(&self, writer: &mut W) -> io::Result<()> {
+        self.write_async_with_options(writer, &WriteOptions::default())
+            .await
+    }
+
+    /// Async counterpart of [PLYFile::write_with_options]
+    ///
+    /// Header and rows are encoded by the synchronous writer into a buffer,
+    /// which is written to `writer` in chunks.
+    /// Compressed output is encoded whole before it is written.
+    pub async fn write_async_with_options<W: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+    ) -> io::Result<()> {
+        if options.compression != Compression::None {
+            let mut buf = BufWriter::new(Vec::new());
+            self.write_with_options(&mut buf, options)?;
+            writer.write_all(buf.get_ref()).await?;
+            return writer.flush().await;
+        }
+        let mut buf = Vec::with_capacity(CHUNK_BYTES);
+        self.write_header_with(&mut buf, options)?;
+        for element in self.elements.iter() {
+            match element {
+                Element::Element { elements: e, .. } => {
+                    write_element_async(e, &self.format, &options.ascii, &mut buf, writer).await?
+                }
+                Element::ListElement { elements: e, .. } => {
+                    write_element_async(e, &self.format, &options.ascii, &mut buf, writer).await?
+                }
+                Element::MixedElement { elements: e, .. } => {
+                    write_element_async(e, &self.format, &options.ascii, &mut buf, writer).await?
+                }
+            }
+        }
+        writer.write_all(&buf).await?;
+        writer.flush().await
+    }
+}
+
+async fn write_element_async<P, W>(
+    element: &GenericElement<P, P::Payload>,
+    format: &Format,
+    ascii: &AsciiOptions,
+    buf: &mut Vec<u8>,
+    writer: &mut W,
+) -> io::Result<()>
+where
+    P: WritePayload<Vec<u8>>,
+    W: AsyncWrite + Unpin,
+{
+    let formats = element.property().ascii_formats(ascii);
+    for payload in element.payload() {
+        if let Err(e) = encode_row(element.property(), payload, &formats, buf, format) {
+            writer.write_all(buf).await?;
+            return Err(e);
+        }
+        if buf.len() >= CHUNK_BYTES {
+            writer.write_all(buf).await?;
+            buf.clear();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Format, GenericElement, PLYFile, PLYValueTypeName, Payload, PropertyList};
+
+    #[tokio::test]
+    async fn test_write_async() {
+        let mut ply = PLYFile::new(Format::BinaryLittleEndian {
+            version: "1.0".to_string(),
+        });
+        let mut element = GenericElement::new(PropertyList::new(
+            "vertex_indices",
+            PLYValueTypeName::Uchar,
+            PLYValueTypeName::Int,
+        ));
+        for i in 0..10_000 {
+            element
+                .push_payload(Payload::new(vec![i.into(), (i + 1).into(), (i + 2).into()]))
+                .unwrap();
+        }
+        ply.elements.push(crate::Element::ListElement {
+            name: "face".to_string(),
+            elements: element,
+        });
+
+        let mut written = Vec::new();
+        ply.write_async(&mut written).await.unwrap();
+        let mut expected = std::io::BufWriter::new(Vec::new());
+        ply.write(&mut expected).unwrap();
+        assert_eq!(written, expected.into_inner().unwrap());
+        assert_eq!(PLYFile::from_bytes(&written).unwrap(), ply);
+    }
+}
@@ -1,5 +1,12 @@
 use std::io::Write;
 
+use super::WriteOptions;
+
 pub(crate) trait PlyWriteHeader<T: Write> {
     fn write_header(&self, writer: &mut T) -> std::io::Result<()>;
+
+    /// Write header with [WriteOptions], by default options are ignored
+    fn write_header_with(&self, writer: &mut T, _options: &WriteOptions) -> std::io::Result<()> {
+        self.write_header(writer)
+    }
 }
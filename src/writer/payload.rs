@@ -1,31 +1,151 @@
-use std::io::Write;
+use std::io::{self, Write};
 
-use crate::{Format, GenericElement, Payload};
+use crate::{Format, GenericElement, PLYValue};
 
-pub(crate) fn write_element_payload<T: Write, P: WritePayload<T, Payload = Payload>>(
-    element: &GenericElement<P>,
+use super::ascii::{AsciiOptions, FloatFormat};
+
+/// Encoded rows are written at once when buffer reaches this size
+///
+/// Small enough to stay in L1 cache, and larger than the default capacity of [BufWriter](std::io::BufWriter)
+/// so chunks are passed to its inner writer without being copied.
+const CHUNK_BYTES: usize = 16 * 1024;
+
+pub(crate) fn write_element_payload<T: Write, P: WritePayload<T>>(
+    element: &GenericElement<P, P::Payload>,
     writer: &mut T,
     format: &Format,
-) -> std::io::Result<()> {
+    ascii: &AsciiOptions,
+) -> io::Result<()> {
     match format {
         Format::Ascii { .. } => {
+            let formats = element.property().ascii_formats(ascii);
             for payload in element.payload() {
-                element.property().write_payload_ascii(payload, writer)?;
+                element
+                    .property()
+                    .write_payload_ascii(payload, &formats, writer)?;
             }
+            Ok(())
         }
+        Format::BinaryBigEndian { .. } => write_encoded(element, writer, P::encode_be),
+        Format::BinaryLittleEndian { .. } => write_encoded(element, writer, P::encode_le),
+    }
+}
 
-        Format::BinaryBigEndian { .. } => {
-            for payload in element.payload() {
-                element.property().write_payload_be(payload, writer)?;
-            }
+/// Encode rows into a contiguous buffer, which is written by `write_all` in chunks
+fn write_encoded<T, P, F>(
+    element: &GenericElement<P, P::Payload>,
+    writer: &mut T,
+    encode: F,
+) -> io::Result<()>
+where
+    T: Write,
+    P: WritePayload<T>,
+    F: Fn(&P, &P::Payload, &mut Vec<u8>) -> io::Result<()>,
+{
+    let mut buf = Vec::with_capacity(CHUNK_BYTES);
+    for payload in element.payload() {
+        let len = buf.len();
+        if let Err(e) = encode(element.property(), payload, &mut buf) {
+            // rows before the failing one are written, as in ascii
+            buf.truncate(len);
+            writer.write_all(&buf)?;
+            return Err(e);
         }
-        Format::BinaryLittleEndian { .. } => {
-            for payload in element.payload() {
-                element.property().write_payload_le(payload, writer)?;
-            }
+        if buf.len() >= CHUNK_BYTES {
+            writer.write_all(&buf)?;
+            buf.clear();
         }
+    }
+    writer.write_all(&buf)
+}
+
+/// Append single row to `buf`, as ascii line or binary bytes
+///
+/// `buf` is left as it was when the row fails part-way.
+pub(crate) fn encode_row<P: WritePayload<Vec<u8>>>(
+    props: &P,
+    payload: &P::Payload,
+    formats: &[FloatFormat],
+    buf: &mut Vec<u8>,
+    format: &Format,
+) -> io::Result<()> {
+    let len = buf.len();
+    let result = match format {
+        Format::Ascii { .. } => props.write_payload_ascii(payload, formats, buf),
+        Format::BinaryBigEndian { .. } => props.encode_be(payload, buf),
+        Format::BinaryLittleEndian { .. } => props.encode_le(payload, buf),
     };
-    Ok(())
+    if result.is_err() {
+        buf.truncate(len);
+    }
+    result
+}
+
+/// Append value as big-endian bytes to `buf`
+#[inline]
+pub(crate) fn encode_value_be(value: &PLYValue, buf: &mut Vec<u8>) {
+    match value {
+        PLYValue::Char(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        PLYValue::Uchar(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        PLYValue::Short(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        PLYValue::Ushort(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        PLYValue::Int(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        PLYValue::Uint(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        PLYValue::Float(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        PLYValue::Double(v) => buf.extend_from_slice(&v.to_be_bytes()),
+    }
+}
+
+/// Append values as big-endian bytes to `buf`
+#[inline]
+pub(crate) fn encode_values_be(values: &[PLYValue], buf: &mut Vec<u8>) {
+    for value in values {
+        encode_value_be(value, buf);
+    }
+}
+
+/// Append value as little-endian bytes to `buf`
+#[inline]
+pub(crate) fn encode_value_le(value: &PLYValue, buf: &mut Vec<u8>) {
+    match value {
+        PLYValue::Char(v) => buf.extend_from_slice(&v.to_le_bytes()),
+        PLYValue::Uchar(v) => buf.extend_from_slice(&v.to_le_bytes()),
+        PLYValue::Short(v) => buf.extend_from_slice(&v.to_le_bytes()),
+        PLYValue::Ushort(v) => buf.extend_from_slice(&v.to_le_bytes()),
+        PLYValue::Int(v) => buf.extend_from_slice(&v.to_le_bytes()),
+        PLYValue::Uint(v) => buf.extend_from_slice(&v.to_le_bytes()),
+        PLYValue::Float(v) => buf.extend_from_slice(&v.to_le_bytes()),
+        PLYValue::Double(v) => buf.extend_from_slice(&v.to_le_bytes()),
+    }
+}
+
+/// Append values as little-endian bytes to `buf`
+#[inline]
+pub(crate) fn encode_values_le(values: &[PLYValue], buf: &mut Vec<u8>) {
+    for value in values {
+        encode_value_le(value, buf);
+    }
+}
+
+pub(crate) trait WritePayload<T: Write> {
+    type Payload;
+
+    /// Write row as ascii line, values of each property in its format of [WritePayload::ascii_formats]
+    fn write_payload_ascii(
+        &self,
+        payload: &Self::Payload,
+        formats: &[FloatFormat],
+        writer: &mut T,
+    ) -> io::Result<()>;
+
+    /// Format of values of each property in ascii payload
+    fn ascii_formats(&self, options: &AsciiOptions) -> Vec<FloatFormat>;
+
+    /// Append row as big-endian bytes to `buf`
+    fn encode_be(&self, payload: &Self::Payload, buf: &mut Vec<u8>) -> io::Result<()>;
+
+    /// Append row as little-endian bytes to `buf`
+    fn encode_le(&self, payload: &Self::Payload, buf: &mut Vec<u8>) -> io::Result<()>;
 }
 
 #[cfg(test)]
@@ -47,6 +167,7 @@ mod test {
                     PLYValueTypeName::Uchar,
                 ],
                 names: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+                namings: vec![TypeNaming::Classic; 3],
             },
             payloads: vec![
                 Payload(vec![
@@ -98,6 +219,7 @@ mod test {
             &Format::Ascii {
                 version: "1.0".to_string(),
             },
+            &AsciiOptions::default(),
         )
         .unwrap();
         assert_eq!(
@@ -116,14 +238,57 @@ mod test {
             writer.into_inner().unwrap(),
         );
     }
-}
 
-pub(crate) trait WritePayload<T: Write> {
-    type Payload;
+    /// Writer accepting at most 3 bytes at once
+    struct ShortWriter(Vec<u8>);
 
-    fn write_payload_ascii(&self, payload: &Self::Payload, writer: &mut T) -> std::io::Result<()>;
+    impl std::io::Write for ShortWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(3);
+            self.0.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
 
-    fn write_payload_be(&self, payload: &Self::Payload, writer: &mut T) -> std::io::Result<()>;
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_element_payload_binary() {
+        use crate::*;
+        let mut element = GenericElement::new(PropertyList::new(
+            "vertex_indices",
+            PLYValueTypeName::Uchar,
+            PLYValueTypeName::Int,
+        ));
+        element
+            .push_payload(Payload::new(vec![PLYValue::Int(1), PLYValue::Int(-2)]))
+            .unwrap();
+        let format = Format::BinaryLittleEndian {
+            version: "1.0".to_string(),
+        };
 
-    fn write_payload_le(&self, payload: &Self::Payload, writer: &mut T) -> std::io::Result<()>;
+        let mut writer = ShortWriter(Vec::new());
+        write_element_payload(&element, &mut writer, &format, &AsciiOptions::default()).unwrap();
+        assert_eq!(writer.0, [2, 1, 0, 0, 0, 0xfe, 0xff, 0xff, 0xff]);
+
+        let mut writer = ShortWriter(Vec::new());
+        let format = Format::BinaryBigEndian {
+            version: "1.0".to_string(),
+        };
+        write_element_payload(&element, &mut writer, &format, &AsciiOptions::default()).unwrap();
+        assert_eq!(writer.0, [2, 0, 0, 0, 1, 0xff, 0xff, 0xff, 0xfe]);
+
+        // list length does not fit in uchar
+        element
+            .push_payload(Payload::new(vec![PLYValue::Int(0); 256]))
+            .unwrap();
+        let result =
+            write_element_payload(&element, &mut writer, &format, &AsciiOptions::default());
+        assert_eq!(
+            result.map_err(|e| e.kind()),
+            Err(std::io::ErrorKind::InvalidInput)
+        );
+    }
 }
@@ -0,0 +1,381 @@
+use std::io::Write;
+
+use crate::{
+    compression::Compressed,
+    error::{PLYError, PLYResult},
+    FloatFormat, Format, MixedPayload, MixedProperty, MixedValue, PLYFile, PropertyKind,
+};
+
+use super::{
+    header::PlyWriteHeader,
+    payload::{encode_row, WritePayload},
+    WriteOptions,
+};
+
+/// Element declared in header of [PLYWriter]
+struct Declared {
+    name: String,
+    count: usize,
+    props: MixedProperty,
+    /// formats of properties in ascii payload
+    formats: Vec<FloatFormat>,
+}
+
+/// Streaming PLY writer
+///
+/// Header is written on creation from a schema declaring elements with their counts,
+/// then rows are written element by element as they are produced,
+/// so memory usage does not depend on the number of rows.
+/// Each row is encoded and written to `writer` at once, wrap it in [BufWriter](std::io::BufWriter).
+///
+/// ```no_run
+/// # fn main() -> ply::error::PLYResult<()> {
+/// use std::{fs::File, io::BufWriter};
+/// use ply::*;
+///
+/// let mut props = Property::new();
+/// props.push_prop("x", PLYValueTypeName::Float);
+/// let mut schema = PLYFile::new(Format::BinaryLittleEndian { version: "1.0".to_string() });
+/// schema.elements.push(Element::Element {
+///     name: "vertex".to_string(),
+///     elements: GenericElement::with_count(props, 1_000_000),
+/// });
+///
+/// let mut writer = PLYWriter::new(BufWriter::new(File::create("points.ply")?), &schema)?;
+/// let mut vertex = writer.element("vertex")?;
+/// for i in 0..1_000_000 {
+///     vertex.write_row(&MixedPayload::new(vec![MixedValue::Scalar((i as f32).into())]))?;
+/// }
+/// writer.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PLYWriter<W: Write> {
+    writer: Compressed<W>,
+    format: Format,
+    elements: Vec<Declared>,
+    /// index of the next element to write
+    next: usize,
+    /// rows written of element `next - 1`
+    written: usize,
+    /// encoded row
+    buf: Vec<u8>,
+}
+
+impl<W: Write> PLYWriter<W> {
+    /// Write header declared by `schema`, payload of its elements is not written
+    pub fn new(writer: W, schema: &PLYFile) -> PLYResult<Self> {
+        PLYWriter::with_options(writer, schema, &WriteOptions::default())
+    }
+
+    /// Write header declared by `schema` with `options`, compressed if [WriteOptions::compression] is set
+    ///
+    /// Names of `schema` are checked as by [PLYFile::validate] before header is written.
+    pub fn with_options(writer: W, schema: &PLYFile, options: &WriteOptions) -> PLYResult<Self> {
+        schema.validate_header()?;
+        let mut writer = Compressed::new(writer, options.compression)?;
+        schema.write_header_with(&mut writer, options)?;
+        let elements = schema
+            .elements
+            .iter()
+            .map(|element| {
+                let props = element.to_mixed_property();
+                Declared {
+                    name: element.name().to_string(),
+                    count: element.count(),
+                    formats: WritePayload::<Vec<u8>>::ascii_formats(&props, &options.ascii),
+                    props,
+                }
+            })
+            .collect();
+        Ok(Self {
+            writer,
+            format: schema.format.clone(),
+            elements,
+            next: 0,
+            written: 0,
+            buf: Vec::new(),
+        })
+    }
+
+    /// Start writing rows of element `name`
+    ///
+    /// Elements are written in the order declared in header, elements with no rows may be left out.
+    /// All rows of the previous element must be written.
+    pub fn element(&mut self, name: &str) -> PLYResult<ElementWriter<'_, W>> {
+        self.check_written()?;
+        let rest = &self.elements[self.next..];
+        match rest.iter().position(|e| e.count > 0 || e.name == name) {
+            Some(i) if rest[i].name == name => {
+                self.next += i + 1;
+                self.written = 0;
+                Ok(ElementWriter { writer: self })
+            }
+            Some(i) => Err(PLYError::ElementOrder {
+                element: name.to_string(),
+                expected: Some(rest[i].name.clone()),
+            }),
+            None => Err(PLYError::ElementOrder {
+                element: name.to_string(),
+                expected: None,
+            }),
+        }
+    }
+
+    /// Check that rows of all elements were written, and returns `writer`
+    ///
+    /// Compressed output is completed here, dropping [PLYWriter] without finishing leaves it incomplete.
+    pub fn finish(self) -> PLYResult<W> {
+        self.check_written()?;
+        if let Some(element) = self.elements[self.next..].iter().find(|e| e.count > 0) {
+            return Err(PLYError::RowCount {
+                element: element.name.clone(),
+                declared: element.count,
+                written: 0,
+            });
+        }
+        let mut writer = self.writer.finish()?;
+        writer.flush()?;
+        Ok(writer)
+    }
+
+    /// Check that rows of the current element were written
+    fn check_written(&self) -> PLYResult<()> {
+        match self.next.checked_sub(1).map(|i| &self.elements[i]) {
+            Some(element) if self.written < element.count => Err(PLYError::RowCount {
+                element: element.name.clone(),
+                declared: element.count,
+                written: self.written,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Writer of rows of an element, returned by [PLYWriter::element]
+pub struct ElementWriter<'a, W: Write> {
+    writer: &'a mut PLYWriter<W>,
+}
+
+impl<'a, W: Write> ElementWriter<'a, W> {
+    fn declared(&self) -> &Declared {
+        &self.writer.elements[self.writer.next - 1]
+    }
+
+    pub fn name(&self) -> &str {
+        &self.declared().name
+    }
+
+    /// Returns count of rows declared in header
+    pub fn count(&self) -> usize {
+        self.declared().count
+    }
+
+    /// Write next row
+    ///
+    /// Row is checked before anything is written: it must have values of types declared in header,
+    /// list lengths must fit in their count type, and no more rows than declared may be written.
+    /// A list too long for its count type is returned as [PLYError::Invalid] like [PLYFile::validate].
+    pub fn write_row(&mut self, row: &MixedPayload) -> PLYResult<()> {
+        let writer = &mut *self.writer;
+        let declared = &writer.elements[writer.next - 1];
+        if writer.written >= declared.count {
+            return Err(PLYError::RowCount {
+                element: declared.name.clone(),
+                declared: declared.count,
+                written: writer.written + 1,
+            });
+        }
+        declared.props.check_payload(row)?;
+        for (value, kind) in row.iter().zip(declared.props.kinds.iter()) {
+            if let (MixedValue::List(list), PropertyKind::List { count, .. }) = (value, kind) {
+                crate::list_len(*count, list.len()).map_err(|e| PLYError::Invalid {
+                    element: declared.name.clone(),
+                    row: Some(writer.written),
+                    reason: e.to_string(),
+                })?;
+            }
+        }
+        writer.buf.clear();
+        encode_row(
+            &declared.props,
+            row,
+            &declared.formats,
+            &mut writer.buf,
+            &writer.format,
+        )?;
+        writer.writer.write_all(&writer.buf)?;
+        writer.written += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::BufWriter;
+
+    use super::PLYWriter;
+    use crate::{
+        error::PLYError, Element, Format, GenericElement, MixedPayload, MixedValue, PLYFile,
+        PLYValue, PLYValueTypeName, Payload, Property, PropertyList,
+    };
+
+    fn ply(format: Format) -> PLYFile {
+        let mut ply = PLYFile::new(format);
+        let mut props = Property::new();
+        props.push_prop("x", PLYValueTypeName::Float);
+        props.push_prop("y", PLYValueTypeName::Float);
+        let mut vertex = GenericElement::new(props);
+        for i in 0..3 {
+            let payload = Payload::new(vec![(i as f32).into(), (i as f32 + 0.5).into()]);
+            vertex.push_payload(payload).unwrap();
+        }
+        ply.elements.push(Element::Element {
+            name: "vertex".to_string(),
+            elements: vertex,
+        });
+        ply.elements.push(Element::Element {
+            name: "edge".to_string(),
+            elements: GenericElement::new(Property::new()),
+        });
+        let mut face = GenericElement::new(PropertyList::new(
+            "vertex_indices",
+            PLYValueTypeName::Uchar,
+            PLYValueTypeName::Int,
+        ));
+        face.push_payload(Payload::new(vec![0.into(), 1.into(), 2.into()]))
+            .unwrap();
+        ply.elements.push(Element::ListElement {
+            name: "face".to_string(),
+            elements: face,
+        });
+        ply
+    }
+
+    /// Header of `ply` with counts, and its rows by element
+    fn schema(ply: &PLYFile) -> (PLYFile, Vec<Vec<MixedPayload>>) {
+        let mut schema = ply.clone();
+        let mut rows = Vec::new();
+        for element in schema.elements.iter_mut() {
+            let mixed = match element.clone() {
+                Element::Element { elements, .. } => GenericElement::from(elements),
+                Element::ListElement { elements, .. } => GenericElement::from(elements),
+                Element::MixedElement { elements, .. } => elements,
+            };
+            let props = mixed.property().clone();
+            rows.push(mixed.into_payload());
+            *element = Element::MixedElement {
+                name: element.name().to_string(),
+                elements: GenericElement::with_count(props, element.count()),
+            };
+        }
+        (schema, rows)
+    }
+
+    #[test]
+    fn test_stream_write_same_as_write() {
+        for format in [
+            Format::Ascii {
+                version: "1.0".to_string(),
+            },
+            Format::BinaryBigEndian {
+                version: "1.0".to_string(),
+            },
+        ] {
+            let ply = ply(format);
+            let (schema, rows) = schema(&ply);
+            let mut writer = PLYWriter::new(Vec::new(), &schema).unwrap();
+            for (element, rows) in ["vertex", "face"].iter().zip([&rows[0], &rows[2]]) {
+                let mut element = writer.element(element).unwrap();
+                for row in rows {
+                    element.write_row(row).unwrap();
+                }
+            }
+            let written = writer.finish().unwrap();
+
+            let mut expected = BufWriter::new(Vec::new());
+            ply.write(&mut expected).unwrap();
+            assert_eq!(written, expected.into_inner().unwrap());
+            assert_eq!(PLYFile::from_bytes(&written).unwrap(), ply);
+        }
+    }
+
+    #[test]
+    fn test_stream_write_errors() {
+        let (schema, rows) = schema(&ply(Format::Ascii {
+            version: "1.0".to_string(),
+        }));
+
+        let mut writer = PLYWriter::new(Vec::new(), &schema).unwrap();
+        assert_eq!(
+            writer.element("face").err(),
+            Some(PLYError::ElementOrder {
+                element: "face".to_string(),
+                expected: Some("vertex".to_string()),
+            })
+        );
+        let mut vertex = writer.element("vertex").unwrap();
+        assert_eq!(vertex.count(), 3);
+        vertex.write_row(&rows[0][0]).unwrap();
+        let int_row = MixedPayload::new(vec![
+            MixedValue::Scalar(PLYValue::Int(0)),
+            MixedValue::Scalar(PLYValue::Int(0)),
+        ]);
+        assert_eq!(vertex.write_row(&int_row), Err(PLYError::MissmatchDataType));
+        assert_eq!(
+            vertex.write_row(&rows[2][0]),
+            Err(PLYError::PropertyLengthErr)
+        );
+        assert_eq!(
+            writer.element("face").err(),
+            Some(PLYError::RowCount {
+                element: "vertex".to_string(),
+                declared: 3,
+                written: 1,
+            })
+        );
+        // rejected rows are not written
+        assert_eq!(writer.written, 1);
+
+        let mut writer = PLYWriter::new(Vec::new(), &schema).unwrap();
+        let mut vertex = writer.element("vertex").unwrap();
+        for row in &rows[0] {
+            vertex.write_row(row).unwrap();
+        }
+        assert_eq!(
+            vertex.write_row(&rows[0][0]),
+            Err(PLYError::RowCount {
+                element: "vertex".to_string(),
+                declared: 3,
+                written: 4,
+            })
+        );
+        let mut face = writer.element("face").unwrap();
+        let long = MixedPayload::new(vec![MixedValue::List(vec![PLYValue::Int(0); 256])]);
+        assert_eq!(
+            face.write_row(&long),
+            Err(PLYError::Invalid {
+                element: "face".to_string(),
+                row: Some(0),
+                reason: "list length 256 does not fit in uchar".to_string(),
+            })
+        );
+        assert_eq!(
+            writer.finish().err(),
+            Some(PLYError::RowCount {
+                element: "face".to_string(),
+                declared: 1,
+                written: 0,
+            })
+        );
+
+        let mut writer = PLYWriter::new(Vec::new(), &schema).unwrap();
+        assert_eq!(
+            writer.element("missing").err(),
+            Some(PLYError::ElementOrder {
+                element: "missing".to_string(),
+                expected: Some("vertex".to_string()),
+            })
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use std::{collections::HashSet, io::BufWriter, io::Write};
+
+use crate::{
+    error::{PLYError, PLYResult},
+    Element, MixedProperty, MixedValue, PLYFile, PLYValue, PropertyKind,
+};
+
+use super::WriteOptions;
+
+impl PLYFile {
+    /// Check that the file can be written as a consistent PLY file
+    ///
+    /// Returns the first problem found:
+    /// - [PLYError::Invalid] for an element or property name which is empty or contains whitespace,
+    ///   a property declared twice in an element,
+    ///   a row which does not have a value of declared type for each property,
+    ///   or a list whose length does not fit in its count type
+    /// - [PLYError::RowCount] if count of an element differs from its number of rows
+    pub fn validate(&self) -> PLYResult<()> {
+        self.validate_header()?;
+        for element in self.elements.iter() {
+            validate_payload(element)?;
+        }
+        Ok(())
+    }
+
+    /// [PLYFile::write_with_options] after [PLYFile::validate], nothing is written if it fails
+    pub fn write_checked<T: Write>(
+        &self,
+        writer: &mut BufWriter<T>,
+        options: &WriteOptions,
+    ) -> PLYResult<()> {
+        self.validate()?;
+        Ok(self.write_with_options(writer, options)?)
+    }
+
+    /// Check names of elements and properties, payload is not checked
+    pub(crate) fn validate_header(&self) -> PLYResult<()> {
+        for element in self.elements.iter() {
+            let name = element.name();
+            if !is_valid_name(name) {
+                return Err(invalid(
+                    name,
+                    None,
+                    format!("invalid element name {name:?}"),
+                ));
+            }
+            let mut names = HashSet::new();
+            for (prop, _) in element.to_mixed_property().iter() {
+                if !is_valid_name(prop) {
+                    return Err(invalid(
+                        name,
+                        None,
+                        format!("invalid property name {prop:?}"),
+                    ));
+                }
+                if !names.insert(prop.to_string()) {
+                    return Err(invalid(name, None, format!("duplicate property {prop:?}")));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Name is a single word of header line
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+fn invalid(element: &str, row: Option<usize>, reason: String) -> PLYError {
+    PLYError::Invalid {
+        element: element.to_string(),
+        row,
+        reason,
+    }
+}
+
+/// Value of a row, borrowed from any payload type
+enum ValueRef<'a> {
+    Scalar(&'a PLYValue),
+    List(&'a [PLYValue]),
+}
+
+fn validate_payload(element: &Element) -> PLYResult<()> {
+    if element.count() != element.payload_len() {
+        return Err(PLYError::RowCount {
+            element: element.name().to_string(),
+            declared: element.count(),
+            written: element.payload_len(),
+        });
+    }
+    let props = element.to_mixed_property();
+    let result = match element {
+        Element::Element { elements, .. } => {
+            elements
+                .payload()
+                .iter()
+                .enumerate()
+                .try_for_each(|(i, row)| {
+                    validate_row(&props, row.iter().map(ValueRef::Scalar)).map_err(|e| (i, e))
+                })
+        }
+        Element::ListElement { elements, .. } => elements
+            .payload()
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, row)| {
+                validate_row(&props, std::iter::once(ValueRef::List(&row.0))).map_err(|e| (i, e))
+            }),
+        Element::MixedElement { elements, .. } => elements
+            .payload()
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, row)| {
+                let values = row.iter().map(|v| match v {
+                    MixedValue::Scalar(v) => ValueRef::Scalar(v),
+                    MixedValue::List(list) => ValueRef::List(list),
+                });
+                validate_row(&props, values).map_err(|e| (i, e))
+            }),
+    };
+    result.map_err(|(row, reason)| invalid(element.name(), Some(row), reason))
+}
+
+/// Check that each property has a value of its type, returns reason if not
+fn validate_row<'a>(
+    props: &MixedProperty,
+    values: impl ExactSizeIterator<Item = ValueRef<'a>>,
+) -> Result<(), String> {
+    if values.len() != props.len() {
+        return Err(format!("{} values, expected {}", values.len(), props.len()));
+    }
+    for (value, (name, kind)) in values.zip(props.iter()) {
+        match (value, kind) {
+            (ValueRef::Scalar(v), PropertyKind::Scalar(t)) if v.value_type() != t => {
+                return Err(format!(
+                    "property {name:?} is {}, expected {}",
+                    v.value_type().to_str(),
+                    t.to_str()
+                ))
+            }
+            (ValueRef::List(list), PropertyKind::List { count, prop }) => {
+                if let Some(v) = list.iter().find(|v| v.value_type() != prop) {
+                    return Err(format!(
+                        "list {name:?} has {}, expected {}",
+                        v.value_type().to_str(),
+                        prop.to_str()
+                    ));
+                }
+                crate::list_len(count, list.len()).map_err(|e| e.to_string())?;
+            }
+            (ValueRef::Scalar(_), PropertyKind::List { .. }) => {
+                return Err(format!("property {name:?} is a list, found scalar"))
+            }
+            (ValueRef::List(_), PropertyKind::Scalar(_)) => {
+                return Err(format!("property {name:?} is scalar, found list"))
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::BufWriter;
+
+    use crate::{
+        error::PLYError, Element, Format, GenericElement, MixedPayload, MixedProperty, MixedValue,
+        PLYFile, PLYValue, PLYValueTypeName, Payload, Property, PropertyList, WriteOptions,
+    };
+
+    fn ply() -> PLYFile {
+        let mut ply = PLYFile::new(Format::BinaryLittleEndian {
+            version: "1.0".to_string(),
+        });
+        let mut props = Property::new();
+        props.push_prop("x", PLYValueTypeName::Float);
+        props.push_prop("y", PLYValueTypeName::Float);
+        let mut vertex = GenericElement::new(props);
+        vertex
+            .push_payload(Payload::new(vec![0f32.into(), 1f32.into()]))
+            .unwrap();
+        ply.elements.push(Element::Element {
+            name: "vertex".to_string(),
+            elements: vertex,
+        });
+        let mut face = GenericElement::new(PropertyList::new(
+            "vertex_indices",
+            PLYValueTypeName::Uchar,
+            PLYValueTypeName::Int,
+        ));
+        face.push_payload(Payload::new(vec![0.into(), 1.into(), 2.into()]))
+            .unwrap();
+        ply.elements.push(Element::ListElement {
+            name: "face".to_string(),
+            elements: face,
+        });
+        ply
+    }
+
+    fn invalid(element: &str, row: Option<usize>, reason: &str) -> PLYError {
+        PLYError::Invalid {
+            element: element.to_string(),
+            row,
+            reason: reason.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let ply = ply();
+        assert_eq!(ply.validate(), Ok(()));
+
+        let mut bad = ply.clone();
+        if let Element::Element { elements, .. } = &mut bad.elements[0] {
+            elements.count = 8;
+        }
+        assert_eq!(
+            bad.validate(),
+            Err(PLYError::RowCount {
+                element: "vertex".to_string(),
+                declared: 8,
+                written: 1,
+            })
+        );
+
+        let mut bad = ply.clone();
+        if let Element::Element { elements, .. } = &mut bad.elements[0] {
+            elements.payloads[0] = Payload::new(vec![0f32.into(), 1i32.into()]);
+        }
+        assert_eq!(
+            bad.validate(),
+            Err(invalid(
+                "vertex",
+                Some(0),
+                "property \"y\" is int, expected float"
+            ))
+        );
+
+        let mut bad = ply.clone();
+        if let Element::Element { elements, .. } = &mut bad.elements[0] {
+            elements.payloads[0] = Payload::new(vec![0f32.into()]);
+        }
+        assert_eq!(
+            bad.validate(),
+            Err(invalid("vertex", Some(0), "1 values, expected 2"))
+        );
+
+        let mut bad = ply.clone();
+        if let Element::ListElement { elements, .. } = &mut bad.elements[1] {
+            elements.payloads[0] = Payload::new(vec![PLYValue::Int(0); 256]);
+        }
+        assert_eq!(
+            bad.validate(),
+            Err(invalid(
+                "face",
+                Some(0),
+                "list length 256 does not fit in uchar"
+            ))
+        );
+
+        let mut bad = ply.clone();
+        if let Element::Element { elements, .. } = &mut bad.elements[0] {
+            elements.props.names[1] = "x".to_string();
+        }
+        assert_eq!(
+            bad.validate(),
+            Err(invalid("vertex", None, "duplicate property \"x\""))
+        );
+
+        let mut bad = ply.clone();
+        if let Element::Element { elements, .. } = &mut bad.elements[0] {
+            elements.props.names[1] = "y z".to_string();
+        }
+        assert_eq!(
+            bad.validate(),
+            Err(invalid("vertex", None, "invalid property name \"y z\""))
+        );
+
+        let mut bad = ply.clone();
+        if let Element::ListElement { name, .. } = &mut bad.elements[1] {
+            *name = String::new();
+        }
+        assert_eq!(
+            bad.validate(),
+            Err(invalid("", None, "invalid element name \"\""))
+        );
+
+        let mut props = MixedProperty::new();
+        props.push_list_prop("texcoord", PLYValueTypeName::Uchar, PLYValueTypeName::Float);
+        let mut mixed = PLYFile::new(Format::Ascii {
+            version: "1.0".to_string(),
+        });
+        mixed.elements.push(Element::MixedElement {
+            name: "face".to_string(),
+            elements: GenericElement {
+                count: 1,
+                props,
+                payloads: vec![MixedPayload::new(vec![MixedValue::Scalar(0f32.into())])],
+            },
+        });
+        assert_eq!(
+            mixed.validate(),
+            Err(invalid(
+                "face",
+                Some(0),
+                "property \"texcoord\" is a list, found scalar"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_write_checked() {
+        let ply = ply();
+        let mut writer = BufWriter::new(Vec::new());
+        ply.write_checked(&mut writer, &WriteOptions::default())
+            .unwrap();
+        let mut expected = BufWriter::new(Vec::new());
+        ply.write(&mut expected).unwrap();
+        assert_eq!(writer.into_inner().unwrap(), expected.into_inner().unwrap());
+
+        let mut bad = ply.clone();
+        if let Element::ListElement { elements, .. } = &mut bad.elements[1] {
+            elements.count = 0;
+        }
+        let mut writer = BufWriter::new(Vec::new());
+        assert!(matches!(
+            bad.write_checked(&mut writer, &WriteOptions::default()),
+            Err(PLYError::RowCount { .. })
+        ));
+        assert!(writer.into_inner().unwrap().is_empty());
+    }
+}